@@ -7,7 +7,7 @@ cd evm-project && npx hardhat run --network goerli scripts/deploy.ts && cd ../
 solana config set --url devnet
 
 #change the key pair
-cd solana-project && anchor build && anchor deploy && cd../
+cd solana-project && anchor build -- --features devnet && anchor deploy && cd../
 
 #Register Solana Address on EVM
 cd evm-project && npx hardhat run ./scripts/register_solana_address.ts && cd ../
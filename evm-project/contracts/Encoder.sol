@@ -4,21 +4,53 @@ pragma solidity ^0.8.0;
 import "./Messages.sol";
 
 contract Encoder is Messages {
+    // Version of the payload layout, written right after the message code
+    uint8 public constant PAYLOAD_VERSION = 1;
+
+    // Message codes, mirrored by the MessageType registry in the Solana program (payload.rs).
+    // Every Solana instruction rejects payloads whose code does not match its own.
+    uint8 public constant NATIVE_STREAM = 1;
     uint8 public constant TOKEN_STREAM = 2;
+    uint8 public constant NATIVE_WITHDRAW_STREAM = 3;
     uint8 public constant TOKEN_WITHDRAW_STREAM = 4;
+    uint8 public constant DEPOSIT_NATIVE = 5;
     uint8 public constant DEPOSIT_TOKEN = 6;
+    uint8 public constant PAUSE_NATIVE = 7;
     uint8 public constant PAUSE_TOKEN = 8;
+    uint8 public constant WITHDRAW_NATIVE = 9;
     uint8 public constant WITHDRAW_TOKEN = 10;
+    uint8 public constant INSTANT_NATIVE = 11;
     uint8 public constant INSTANT_TOKEN = 12;
+    uint8 public constant NATIVE_STREAM_UPDATE = 13;
     uint8 public constant TOKEN_STREAM_UPDATE = 14;
+    uint8 public constant CANCEL_NATIVE = 15;
     uint8 public constant CANCEL_TOKEN = 16;
     uint8 public constant DIRECT_TRANSFER = 17;
     uint8 public constant INITIALIZE_PDA = 18;
     uint8 public constant INITIALIZE_TOKEN_ACCOUNT = 19;
 
+    // Reports posted back by the Solana program, consumed by Messenger.receiveMessage
+    uint8 public constant STREAM_CREATED = 20;
+    uint8 public constant STREAM_CANCELLED = 21;
+    uint8 public constant WITHDRAWAL_COMPLETED = 22;
+    uint8 public constant EXECUTION_FAILED = 23;
+    uint8 public constant BATCH_STREAMS_PROCESSED = 26;
+
+    // Memo carried by Token Bridge transfers with payload, consumed by Messenger.receiveTransfer
+    uint8 public constant TRANSFER_MEMO = 24;
+
+    uint8 public constant BATCH_TOKEN_STREAM = 25;
+
+    // How Solana maps a stream or instant transfer receiver to an account, see ReceiverKind
+    uint8 public constant RECEIVER_CHAIN_PDA = 0;
+    uint8 public constant RECEIVER_SOLANA_WALLET = 1;
+    // Mirrors MAX_BATCH_SIZE of the Solana program
+    uint8 public constant MAX_BATCH_SIZE = 8;
+
     function encode_initialize_pda(Messages.InitializePDA memory initializePDA) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             INITIALIZE_PDA,
+            PAYLOAD_VERSION,
             initializePDA.account,
             initializePDA.toChain
         );
@@ -27,6 +59,7 @@ contract Encoder is Messages {
     function encode_initialize_token_account(Messages.InitializeTokenAccount memory initializeTokenAccount) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             INITIALIZE_TOKEN_ACCOUNT,
+            PAYLOAD_VERSION,
             initializeTokenAccount.account,
             initializeTokenAccount.tokenMint,
             initializeTokenAccount.toChain
@@ -36,6 +69,7 @@ contract Encoder is Messages {
     function encode_token_stream(Messages.ProcessStreamToken memory processStream) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             TOKEN_STREAM,
+            PAYLOAD_VERSION,
             processStream.start_time,
             processStream.end_time,
             processStream.amount,
@@ -44,13 +78,43 @@ contract Encoder is Messages {
             processStream.receiver,
             processStream.can_cancel,
             processStream.can_update,
-            processStream.token_mint
+            processStream.token_mint,
+            processStream.stream_id,
+            processStream.decimals,
+            processStream.receiver_kind
+        );
+    }
+
+    function encode_batch_token_stream(Messages.ProcessBatchStreamToken memory processBatch) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            BATCH_TOKEN_STREAM,
+            PAYLOAD_VERSION,
+            processBatch.toChain,
+            processBatch.sender,
+            processBatch.token_mint,
+            processBatch.decimals,
+            uint8(processBatch.items.length)
         );
+        for (uint256 i = 0; i < processBatch.items.length; i++) {
+            Messages.BatchStreamItem memory item = processBatch.items[i];
+            encoded = abi.encodePacked(
+                encoded,
+                item.start_time,
+                item.end_time,
+                item.amount,
+                item.receiver,
+                item.can_cancel,
+                item.can_update,
+                item.stream_id,
+                item.receiver_kind
+            );
+        }
     }
 
     function encode_token_stream_update(Messages.UpdateStreamToken memory processStream) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             TOKEN_STREAM_UPDATE,
+            PAYLOAD_VERSION,
             processStream.start_time,
             processStream.end_time,
             processStream.amount,
@@ -58,82 +122,196 @@ contract Encoder is Messages {
             processStream.sender,
             processStream.receiver,
             processStream.token_mint,
-            processStream.data_account_address
+            processStream.stream_id,
+            processStream.decimals
         );
     }
 
     function encode_token_withdraw_stream(Messages.ProcessWithdrawStreamToken memory processWithdrawStream) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             TOKEN_WITHDRAW_STREAM,
+            PAYLOAD_VERSION,
             processWithdrawStream.toChain,
             processWithdrawStream.withdrawer,
             processWithdrawStream.token_mint,
             processWithdrawStream.sender_address,
-            processWithdrawStream.data_account_address
+            processWithdrawStream.stream_id
         );
     }
 
     function encode_process_deposit_token(Messages.ProcessDepositToken memory processDeposit) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             DEPOSIT_TOKEN,
+            PAYLOAD_VERSION,
             processDeposit.amount,
             processDeposit.toChain,
             processDeposit.depositor,
-            processDeposit.token_mint
+            processDeposit.token_mint,
+            processDeposit.decimals
         );
     }
 
     function encode_process_pause_token_stream(Messages.PauseStreamToken memory pauseStream) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             PAUSE_TOKEN,
+            PAYLOAD_VERSION,
             pauseStream.toChain,
             pauseStream.sender,
             pauseStream.token_mint,
             pauseStream.reciever_address,
-            pauseStream.data_account_address
+            pauseStream.stream_id
         );
     }
 
     function encode_process_cancel_token_stream(Messages.CancelStreamToken memory cancelStream) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             CANCEL_TOKEN,
+            PAYLOAD_VERSION,
             cancelStream.toChain,
             cancelStream.sender,
             cancelStream.token_mint,
             cancelStream.reciever_address,
-            cancelStream.data_account_address
+            cancelStream.stream_id
         );
     }
 
     function encode_process_token_withdrawal(Messages.ProcessWithdrawToken memory processWithdraw) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             WITHDRAW_TOKEN,
+            PAYLOAD_VERSION,
             processWithdraw.amount,
             processWithdraw.toChain,
             processWithdraw.withdrawer,
-            processWithdraw.token_mint
+            processWithdraw.token_mint,
+            processWithdraw.decimals
         );
     }
 
     function encode_process_instant_token_transfer(Messages.ProcessTransferToken memory processTransfer) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             INSTANT_TOKEN,
+            PAYLOAD_VERSION,
             processTransfer.amount,
             processTransfer.toChain,
             processTransfer.sender,
             processTransfer.token_mint,
-            processTransfer.receiver
+            processTransfer.receiver,
+            processTransfer.decimals,
+            processTransfer.receiver_kind
         );
     }
 
-    function encode_process_direct_transfer(Messages.ProcessTransferToken memory processTransfer) public pure returns (bytes memory encoded){
+    function encode_process_direct_transfer(Messages.ProcessDirectTransfer memory processTransfer) public pure returns (bytes memory encoded){
         encoded = abi.encodePacked(
             DIRECT_TRANSFER,
+            PAYLOAD_VERSION,
             processTransfer.amount,
             processTransfer.toChain,
             processTransfer.sender,
             processTransfer.token_mint,
-            processTransfer.receiver
+            processTransfer.receiver,
+            processTransfer.max_relayer_fee,
+            processTransfer.target_chain,
+            processTransfer.decimals
+        );
+    }
+
+    function encode_native_stream(Messages.ProcessStreamNative memory processStream) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            NATIVE_STREAM,
+            PAYLOAD_VERSION,
+            processStream.start_time,
+            processStream.end_time,
+            processStream.amount,
+            processStream.toChain,
+            processStream.sender,
+            processStream.receiver,
+            processStream.can_cancel,
+            processStream.can_update,
+            processStream.stream_id,
+            processStream.decimals
+        );
+    }
+
+    function encode_native_stream_update(Messages.UpdateStreamNative memory processStream) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            NATIVE_STREAM_UPDATE,
+            PAYLOAD_VERSION,
+            processStream.start_time,
+            processStream.end_time,
+            processStream.amount,
+            processStream.toChain,
+            processStream.sender,
+            processStream.receiver,
+            processStream.stream_id,
+            processStream.decimals
+        );
+    }
+
+    function encode_native_withdraw_stream(Messages.ProcessWithdrawStreamNative memory processWithdrawStream) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            NATIVE_WITHDRAW_STREAM,
+            PAYLOAD_VERSION,
+            processWithdrawStream.toChain,
+            processWithdrawStream.withdrawer,
+            processWithdrawStream.sender_address,
+            processWithdrawStream.stream_id
+        );
+    }
+
+    function encode_process_deposit_native(Messages.ProcessDepositNative memory processDeposit) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            DEPOSIT_NATIVE,
+            PAYLOAD_VERSION,
+            processDeposit.amount,
+            processDeposit.toChain,
+            processDeposit.depositor,
+            processDeposit.decimals
+        );
+    }
+
+    function encode_process_pause_native_stream(Messages.PauseStreamNative memory pauseStream) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            PAUSE_NATIVE,
+            PAYLOAD_VERSION,
+            pauseStream.toChain,
+            pauseStream.sender,
+            pauseStream.reciever_address,
+            pauseStream.stream_id
+        );
+    }
+
+    function encode_process_cancel_native_stream(Messages.CancelStreamNative memory cancelStream) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            CANCEL_NATIVE,
+            PAYLOAD_VERSION,
+            cancelStream.toChain,
+            cancelStream.sender,
+            cancelStream.reciever_address,
+            cancelStream.stream_id
+        );
+    }
+
+    function encode_process_native_withdrawal(Messages.ProcessWithdrawNative memory processWithdraw) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            WITHDRAW_NATIVE,
+            PAYLOAD_VERSION,
+            processWithdraw.amount,
+            processWithdraw.toChain,
+            processWithdraw.withdrawer,
+            processWithdraw.decimals
+        );
+    }
+
+    function encode_process_instant_native_transfer(Messages.ProcessTransferNative memory processTransfer) public pure returns (bytes memory encoded){
+        encoded = abi.encodePacked(
+            INSTANT_NATIVE,
+            PAYLOAD_VERSION,
+            processTransfer.amount,
+            processTransfer.toChain,
+            processTransfer.sender,
+            processTransfer.receiver,
+            processTransfer.decimals
         );
     }
 }
\ No newline at end of file
@@ -26,7 +26,8 @@ contract Messages {
         bytes sender;
         bytes receiver;
         bytes token_mint;
-        bytes data_account_address;
+        uint64 stream_id;
+        uint8 decimals;
     }
 
     struct ProcessStreamToken {
@@ -39,6 +40,28 @@ contract Messages {
         uint64 can_cancel;
         uint64 can_update;
         bytes token_mint;
+        uint64 stream_id;
+        uint8 decimals;
+        uint8 receiver_kind;
+    }
+
+    struct BatchStreamItem {
+        uint64 start_time;
+        uint64 end_time;
+        uint64 amount;
+        bytes receiver;
+        uint64 can_cancel;
+        uint64 can_update;
+        uint64 stream_id;
+        uint8 receiver_kind;
+    }
+
+    struct ProcessBatchStreamToken {
+        uint256 toChain;
+        bytes sender;
+        bytes token_mint;
+        uint8 decimals;
+        BatchStreamItem[] items;
     }
 
     struct ProcessWithdrawStreamToken {
@@ -46,7 +69,7 @@ contract Messages {
         bytes withdrawer;
         bytes token_mint;
         bytes sender_address;
-        bytes data_account_address;
+        uint64 stream_id;
     }
 
     struct PauseStreamToken {
@@ -54,7 +77,7 @@ contract Messages {
         bytes sender;
         bytes token_mint;
         bytes reciever_address;
-        bytes data_account_address;
+        uint64 stream_id;
     }
 
     struct CancelStreamToken {
@@ -62,7 +85,7 @@ contract Messages {
         bytes sender;
         bytes token_mint;
         bytes reciever_address;
-        bytes data_account_address;
+        uint64 stream_id;
     }
 
     struct ProcessDepositToken {
@@ -70,6 +93,7 @@ contract Messages {
         uint256 toChain;
         bytes depositor;
         bytes token_mint;
+        uint8 decimals;
     }
 
     struct ProcessTransferToken {
@@ -78,6 +102,19 @@ contract Messages {
         bytes sender;
         bytes token_mint;
         bytes receiver;
+        uint8 decimals;
+        uint8 receiver_kind;
+    }
+
+    struct ProcessDirectTransfer {
+        uint64 amount;
+        uint256 toChain;
+        bytes sender;
+        bytes token_mint;
+        bytes receiver;
+        uint64 max_relayer_fee;
+        uint16 target_chain;
+        uint8 decimals;
     }
 
     struct ProcessWithdrawToken {
@@ -85,6 +122,74 @@ contract Messages {
         uint256 toChain;
         bytes withdrawer;
         bytes token_mint;
+        uint8 decimals;
+    }
+
+    struct ProcessStreamNative {
+        uint64 start_time;
+        uint64 end_time;
+        uint64 amount;
+        uint256 toChain;
+        bytes sender;
+        bytes receiver;
+        uint64 can_cancel;
+        uint64 can_update;
+        uint64 stream_id;
+        uint8 decimals;
+    }
+
+    struct UpdateStreamNative {
+        uint64 start_time;
+        uint64 end_time;
+        uint64 amount;
+        uint256 toChain;
+        bytes sender;
+        bytes receiver;
+        uint64 stream_id;
+        uint8 decimals;
+    }
+
+    struct ProcessWithdrawStreamNative {
+        uint256 toChain;
+        bytes withdrawer;
+        bytes sender_address;
+        uint64 stream_id;
+    }
+
+    struct ProcessDepositNative {
+        uint64 amount;
+        uint256 toChain;
+        bytes depositor;
+        uint8 decimals;
+    }
+
+    struct PauseStreamNative {
+        uint256 toChain;
+        bytes sender;
+        bytes reciever_address;
+        uint64 stream_id;
+    }
+
+    struct CancelStreamNative {
+        uint256 toChain;
+        bytes sender;
+        bytes reciever_address;
+        uint64 stream_id;
+    }
+
+    struct ProcessTransferNative {
+        uint64 amount;
+        uint256 toChain;
+        bytes sender;
+        bytes receiver;
+        uint8 decimals;
+    }
+
+    struct ProcessWithdrawNative {
+        uint64 amount;
+        uint256 toChain;
+        bytes withdrawer;
+        uint8 decimals;
     }
 
 }
\ No newline at end of file
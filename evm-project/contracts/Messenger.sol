@@ -4,6 +4,7 @@ pragma solidity ^0.8.0;
 import "./interfaces/IWormhole.sol";
 import "./interfaces/IWETH.sol";
 import "./interfaces/IERC20.sol";
+import "./interfaces/ITokenBridge.sol";
 import "./Encoder.sol";
 
 contract Messenger is Encoder {
@@ -13,9 +14,12 @@ contract Messenger is Encoder {
     address public owner;
     uint8 public constant CONSISTENCY_LEVEL = 1; //15
     uint32 nonce = 0;
+    // Id of the last token stream started
+    uint64 public _streamId = 0;
 
     IWormhole public _wormhole;
     IWETH public _weth;
+    ITokenBridge public _tokenBridge;
 
     uint256 public _arbiter_fee;
 
@@ -24,8 +28,14 @@ contract Messenger is Encoder {
     
     mapping(uint16 => bytes32) public _applicationContracts;
 
+    // Solana program that sends Token Bridge transfers with a memo, seen as their fromAddress
+    bytes32 public _solanaProgram;
+
+    // Hashes of the Solana reports already consumed
+    mapping(bytes32 => bool) public _completedMessages;
+
     event DepositToken(bytes depositor, bytes tokenMint, uint64 amount, uint32 nonce);
-    event TokenStream(bytes sender, bytes receiver, bytes tokenMint, uint64 amount, uint32 nonce);
+    event TokenStream(bytes sender, bytes receiver, bytes tokenMint, uint64 amount, uint64 streamId, uint32 nonce);
     event TokenStreamUpdate(bytes sender, bytes receiver, bytes tokenMint, uint64 amount, uint32 nonce);
     event WithdrawToken(bytes withdrawer, bytes tokenMint, uint32 nonce);
     event PauseTokenStream(bytes receiver, bytes tokenMint, uint32 nonce);
@@ -34,9 +44,26 @@ contract Messenger is Encoder {
     event TokenWithdrawal(bytes withdrawer, bytes tokenMint, uint64 amount, uint32 nonce);
     event DirectTransfer(bytes sender, bytes receiver, bytes tokenMint, uint64 amount, uint32 nonce);
 
+    event DepositNative(bytes depositor, uint64 amount, uint32 nonce);
+    event NativeStream(bytes sender, bytes receiver, uint64 amount, uint64 streamId, uint32 nonce);
+    event NativeStreamUpdate(bytes sender, bytes receiver, uint64 amount, uint32 nonce);
+    event WithdrawNative(bytes withdrawer, uint32 nonce);
+    event PauseNativeStream(bytes receiver, uint32 nonce);
+    event CancelNativeStream(bytes receiver, uint32 nonce);
+    event InstantNativeTransfer(bytes receiver, uint64 amount, uint32 nonce);
+    event NativeWithdrawal(bytes withdrawer, uint64 amount, uint32 nonce);
+
     event PDAInitialize(bytes account, uint32 nonce);
     event TokenAccountInitialize(bytes account, bytes tokenMint, uint32 nonce);
 
+    // Reports from Solana, sequence is the one returned by publishMessage for the request
+    event StreamCreated(uint64 sequence, bytes32 sender, bytes32 receiver, bytes32 tokenMint, bytes32 dataAccount, uint64 amount, uint64 startTime, uint64 endTime);
+    event StreamCancelled(uint64 sequence, bytes32 depositor, bytes32 receiver, bytes32 tokenMint, bytes32 dataAccount);
+    event WithdrawalCompleted(uint64 sequence, bytes32 withdrawer, bytes32 depositor, bytes32 tokenMint, bytes32 dataAccount);
+    event ExecutionFailed(uint64 sequence, uint8 messageType, uint32 errorCode);
+    event BatchStreamProcessed(uint64 sequence, uint8 index, uint64 streamId, bytes32 dataAccount, uint64 amount, uint32 errorCode);
+    event TransferReceived(uint64 requestSequence, bytes32 sender, uint16 senderChain, address receiver, address token, uint256 amount, uint8 purpose);
+
     constructor(address wormholeAddress, address weth, uint256 arbiter_fee) {
         _wormhole = IWormhole(wormholeAddress); //0x706abc4E45D419950511e474C7B9Ed348A4a716c
         _weth = IWETH(weth); //0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6
@@ -85,7 +112,8 @@ contract Messenger is Encoder {
     function process_deposit_token(
         uint64 amount, 
         bytes memory depositor,
-        bytes memory token_mint
+        bytes memory token_mint,
+        uint8 decimals
     ) public payable {
         nonce++;
         bytes memory encoded_data = Encoder.encode_process_deposit_token(
@@ -93,7 +121,8 @@ contract Messenger is Encoder {
                 amount: amount,
                 toChain: getChainId(),
                 depositor: depositor,
-                token_mint: token_mint
+                token_mint: token_mint,
+                decimals: decimals
             })
         );
          _bridgeInstructionInWormhole(
@@ -112,9 +141,14 @@ contract Messenger is Encoder {
         bytes memory sender,
         uint64 can_cancel,
         uint64 can_update,
-        bytes memory token_mint
+        bytes memory token_mint,
+        uint8 decimals,
+        uint8 receiver_kind
     ) public payable  {
+        require(receiver_kind <= RECEIVER_SOLANA_WALLET, "Invalid receiver kind");
         nonce++;
+        // Seeds the stream data account on Solana, later messages refer to the stream by it
+        uint64 stream_id = ++_streamId;
         bytes memory encoded_data = Encoder.encode_token_stream(
             Messages.ProcessStreamToken({
                 start_time: start_time,
@@ -125,7 +159,10 @@ contract Messenger is Encoder {
                 receiver: receiver,
                 can_cancel: can_cancel,
                 can_update: can_update,
-                token_mint: token_mint
+                token_mint: token_mint,
+                stream_id: stream_id,
+                decimals: decimals,
+                receiver_kind: receiver_kind
             })
         );
          _bridgeInstructionInWormhole(
@@ -133,7 +170,39 @@ contract Messenger is Encoder {
             encoded_data,
             _arbiter_fee
         );
-        emit TokenStream(sender, receiver, token_mint, amount, nonce);
+        emit TokenStream(sender, receiver, token_mint, amount, stream_id, nonce);
+    }
+
+    // Starts up to MAX_BATCH_SIZE streams from the same sender and mint with a single VAA
+    function process_batch_token_stream(
+        bytes memory sender,
+        bytes memory token_mint,
+        uint8 decimals,
+        Messages.BatchStreamItem[] memory items
+    ) public payable  {
+        require(items.length > 0 && items.length <= MAX_BATCH_SIZE, "Invalid batch size");
+        nonce++;
+        for (uint256 i = 0; i < items.length; i++) {
+            require(items[i].receiver_kind <= RECEIVER_SOLANA_WALLET, "Invalid receiver kind");
+            items[i].stream_id = ++_streamId;
+        }
+        bytes memory encoded_data = Encoder.encode_batch_token_stream(
+            Messages.ProcessBatchStreamToken({
+                toChain: getChainId(),
+                sender: sender,
+                token_mint: token_mint,
+                decimals: decimals,
+                items: items
+            })
+        );
+         _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        for (uint256 i = 0; i < items.length; i++) {
+            emit TokenStream(sender, items[i].receiver, token_mint, items[i].amount, items[i].stream_id, nonce);
+        }
     }
 
     function process_token_stream_update(
@@ -143,7 +212,8 @@ contract Messenger is Encoder {
         bytes memory receiver,
         bytes memory sender,
         bytes memory token_mint,
-        bytes memory data_account_address
+        uint64 stream_id,
+        uint8 decimals
     ) public payable  {
         nonce++;
         bytes memory encoded_data = Encoder.encode_token_stream_update(
@@ -155,7 +225,8 @@ contract Messenger is Encoder {
                 sender: sender,
                 receiver: receiver,
                 token_mint: token_mint,
-                data_account_address: data_account_address
+                stream_id: stream_id,
+                decimals: decimals
             })
         );
          _bridgeInstructionInWormhole(
@@ -170,7 +241,7 @@ contract Messenger is Encoder {
         bytes memory withdrawer,
         bytes memory token_mint,
         bytes memory sender_address,
-        bytes memory data_account_address
+        uint64 stream_id
     ) public payable  {
         nonce++;
         bytes memory encoded_data = Encoder.encode_token_withdraw_stream(
@@ -179,7 +250,7 @@ contract Messenger is Encoder {
                 withdrawer: withdrawer,
                 token_mint: token_mint,
                 sender_address: sender_address,
-                data_account_address: data_account_address
+                stream_id: stream_id
             })
         );
          _bridgeInstructionInWormhole(
@@ -194,7 +265,7 @@ contract Messenger is Encoder {
         bytes memory sender,
         bytes memory token_mint,
         bytes memory reciever_address,
-        bytes memory data_account_address
+        uint64 stream_id
     ) public payable  {
         nonce++;
         bytes memory encoded_data = Encoder.encode_process_pause_token_stream(
@@ -203,7 +274,7 @@ contract Messenger is Encoder {
                 sender: sender,
                 token_mint: token_mint,
                 reciever_address: reciever_address,
-                data_account_address: data_account_address
+                stream_id: stream_id
             })
         );
          _bridgeInstructionInWormhole(
@@ -218,7 +289,7 @@ contract Messenger is Encoder {
         bytes memory sender,
         bytes memory token_mint,
         bytes memory reciever_address,
-        bytes memory data_account_address
+        uint64 stream_id
     ) public payable  {
         nonce++;
         bytes memory encoded_data = Encoder.encode_process_cancel_token_stream(
@@ -227,7 +298,7 @@ contract Messenger is Encoder {
                 sender: sender,
                 token_mint: token_mint,
                 reciever_address: reciever_address,
-                data_account_address: data_account_address
+                stream_id: stream_id
             })
         );
          _bridgeInstructionInWormhole(
@@ -243,8 +314,11 @@ contract Messenger is Encoder {
         uint64 amount, 
         bytes memory sender,
         bytes memory withdrawer,
-        bytes memory token_mint
+        bytes memory token_mint,
+        uint8 decimals,
+        uint8 receiver_kind
     ) public payable {
+        require(receiver_kind <= RECEIVER_SOLANA_WALLET, "Invalid receiver kind");
         nonce++;
         bytes memory encoded_data = Encoder.encode_process_instant_token_transfer(
             Messages.ProcessTransferToken({
@@ -252,7 +326,9 @@ contract Messenger is Encoder {
                 toChain: getChainId(),
                 receiver: withdrawer,
                 token_mint: token_mint,
-                sender: sender
+                sender: sender,
+                decimals: decimals,
+                receiver_kind: receiver_kind
             })
         );
          _bridgeInstructionInWormhole(
@@ -267,7 +343,8 @@ contract Messenger is Encoder {
     function process_token_withdrawal(
         uint64 amount, 
         bytes memory sender,
-        bytes memory token_mint
+        bytes memory token_mint,
+        uint8 decimals
     ) public payable {
         nonce++;
         bytes memory encoded_data = Encoder.encode_process_token_withdrawal(
@@ -275,7 +352,8 @@ contract Messenger is Encoder {
                 amount: amount,
                 toChain: getChainId(),
                 withdrawer: sender,
-                token_mint: token_mint
+                token_mint: token_mint,
+                decimals: decimals
             })
         );
          _bridgeInstructionInWormhole(
@@ -290,16 +368,22 @@ contract Messenger is Encoder {
         uint64 amount, 
         bytes memory sender,
         bytes memory token_mint,
-        bytes memory receiver
+        bytes memory receiver,
+        uint64 max_relayer_fee,
+        uint16 target_chain,
+        uint8 decimals
     ) public payable {
         nonce++;
         bytes memory encoded_data = Encoder.encode_process_direct_transfer(
-            Messages.ProcessTransferToken({
+            Messages.ProcessDirectTransfer({
                 amount: amount,
                 toChain: getChainId(),
                 receiver: receiver,
                 token_mint: token_mint,
-                sender: sender
+                sender: sender,
+                max_relayer_fee: max_relayer_fee,
+                target_chain: target_chain,
+                decimals: decimals
             })
         );
          _bridgeInstructionInWormhole(
@@ -310,6 +394,355 @@ contract Messenger is Encoder {
         emit DirectTransfer(sender, receiver, token_mint, amount, nonce);
     }
 
+    function process_deposit_native(
+        uint64 amount,
+        bytes memory depositor,
+        uint8 decimals
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_process_deposit_native(
+            Messages.ProcessDepositNative({
+                amount: amount,
+                toChain: getChainId(),
+                depositor: depositor,
+                decimals: decimals
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit DepositNative(depositor, amount, nonce);
+    }
+
+    function process_native_stream(
+        uint64 start_time,
+        uint64 end_time,
+        uint64 amount,
+        bytes memory receiver,
+        bytes memory sender,
+        uint64 can_cancel,
+        uint64 can_update,
+        uint8 decimals
+    ) public payable {
+        nonce++;
+        // Seeds the stream data account on Solana, later messages refer to the stream by it
+        uint64 stream_id = ++_streamId;
+        bytes memory encoded_data = Encoder.encode_native_stream(
+            Messages.ProcessStreamNative({
+                start_time: start_time,
+                end_time: end_time,
+                amount: amount,
+                toChain: getChainId(),
+                sender: sender,
+                receiver: receiver,
+                can_cancel: can_cancel,
+                can_update: can_update,
+                stream_id: stream_id,
+                decimals: decimals
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit NativeStream(sender, receiver, amount, stream_id, nonce);
+    }
+
+    function process_native_stream_update(
+        uint64 start_time,
+        uint64 end_time,
+        uint64 amount,
+        bytes memory receiver,
+        bytes memory sender,
+        uint64 stream_id,
+        uint8 decimals
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_native_stream_update(
+            Messages.UpdateStreamNative({
+                start_time: start_time,
+                end_time: end_time,
+                amount: amount,
+                toChain: getChainId(),
+                sender: sender,
+                receiver: receiver,
+                stream_id: stream_id,
+                decimals: decimals
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit NativeStreamUpdate(sender, receiver, amount, nonce);
+    }
+
+    function process_native_withdraw_stream(
+        bytes memory withdrawer,
+        bytes memory sender_address,
+        uint64 stream_id
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_native_withdraw_stream(
+            Messages.ProcessWithdrawStreamNative({
+                toChain: getChainId(),
+                withdrawer: withdrawer,
+                sender_address: sender_address,
+                stream_id: stream_id
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit WithdrawNative(withdrawer, nonce);
+    }
+
+    function process_pause_native_stream(
+        bytes memory sender,
+        bytes memory reciever_address,
+        uint64 stream_id
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_process_pause_native_stream(
+            Messages.PauseStreamNative({
+                toChain: getChainId(),
+                sender: sender,
+                reciever_address: reciever_address,
+                stream_id: stream_id
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit PauseNativeStream(sender, nonce);
+    }
+
+    function process_cancel_native_stream(
+        bytes memory sender,
+        bytes memory reciever_address,
+        uint64 stream_id
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_process_cancel_native_stream(
+            Messages.CancelStreamNative({
+                toChain: getChainId(),
+                sender: sender,
+                reciever_address: reciever_address,
+                stream_id: stream_id
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit CancelNativeStream(sender, nonce);
+    }
+
+    // sender will transfer SOL to receiver
+    function process_instant_native_transfer(
+        uint64 amount,
+        bytes memory sender,
+        bytes memory withdrawer,
+        uint8 decimals
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_process_instant_native_transfer(
+            Messages.ProcessTransferNative({
+                amount: amount,
+                toChain: getChainId(),
+                sender: sender,
+                receiver: withdrawer,
+                decimals: decimals
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit InstantNativeTransfer(sender, amount, nonce);
+    }
+
+    // sender will withdraw deposited SOL
+    function process_native_withdrawal(
+        uint64 amount,
+        bytes memory sender,
+        uint8 decimals
+    ) public payable {
+        nonce++;
+        bytes memory encoded_data = Encoder.encode_process_native_withdrawal(
+            Messages.ProcessWithdrawNative({
+                amount: amount,
+                toChain: getChainId(),
+                withdrawer: sender,
+                decimals: decimals
+            })
+        );
+        _bridgeInstructionInWormhole(
+            nonce,
+            encoded_data,
+            _arbiter_fee
+        );
+        emit NativeWithdrawal(sender, amount, nonce);
+    }
+
+    /**
+        Consumes a report posted by the registered Solana application
+     */
+    function receiveMessage(bytes memory encodedVm) public {
+        (Structs.VM memory vm, bool valid, string memory reason) = _wormhole.parseAndVerifyVM(encodedVm);
+        require(valid, reason);
+        require(vm.emitterChainId == SOLANA_CHAIN_ID, "Message is not from Solana");
+        require(_applicationContracts[vm.emitterChainId] == vm.emitterAddress, "Invalid emitter address");
+        require(!_completedMessages[vm.hash], "Message already received");
+        _completedMessages[vm.hash] = true;
+
+        bytes memory payload = vm.payload;
+        // code, version, toChain, sequence
+        require(payload.length >= 42, "Invalid payload length");
+        require(uint8(payload[1]) == PAYLOAD_VERSION, "Unsupported payload version");
+        require(_readUint256(payload, 2) == _wormhole.chainId(), "Message is not for this chain");
+
+        uint8 code = uint8(payload[0]);
+        uint64 sequence = _readUint64(payload, 34);
+        if (code == STREAM_CREATED) {
+            require(payload.length == 194, "Invalid payload length");
+            emit StreamCreated(
+                sequence,
+                _readBytes32(payload, 42),
+                _readBytes32(payload, 74),
+                _readBytes32(payload, 106),
+                _readBytes32(payload, 138),
+                _readUint64(payload, 170),
+                _readUint64(payload, 178),
+                _readUint64(payload, 186)
+            );
+        } else if (code == STREAM_CANCELLED) {
+            require(payload.length == 170, "Invalid payload length");
+            emit StreamCancelled(
+                sequence,
+                _readBytes32(payload, 42),
+                _readBytes32(payload, 74),
+                _readBytes32(payload, 106),
+                _readBytes32(payload, 138)
+            );
+        } else if (code == WITHDRAWAL_COMPLETED) {
+            require(payload.length == 170, "Invalid payload length");
+            emit WithdrawalCompleted(
+                sequence,
+                _readBytes32(payload, 42),
+                _readBytes32(payload, 74),
+                _readBytes32(payload, 106),
+                _readBytes32(payload, 138)
+            );
+        } else if (code == EXECUTION_FAILED) {
+            require(payload.length == 47, "Invalid payload length");
+            emit ExecutionFailed(sequence, uint8(payload[42]), _readUint32(payload, 43));
+        } else if (code == BATCH_STREAMS_PROCESSED) {
+            // count, then stream id, data account, amount and error code of every stream
+            uint8 count = uint8(payload[42]);
+            require(payload.length == 43 + uint256(count) * 52, "Invalid payload length");
+            for (uint8 i = 0; i < count; i++) {
+                uint256 offset = 43 + uint256(i) * 52;
+                emit BatchStreamProcessed(
+                    sequence,
+                    i,
+                    _readUint64(payload, offset),
+                    _readBytes32(payload, offset + 8),
+                    _readUint64(payload, offset + 40),
+                    _readUint32(payload, offset + 48)
+                );
+            }
+        } else {
+            revert("Unknown message type");
+        }
+    }
+
+    /**
+        Redeems a Token Bridge transfer with payload sent by the Solana program and forwards the
+        tokens to the receiver named in its memo
+     */
+    function receiveTransfer(bytes memory encodedVm) public {
+        Structs.VM memory vm = _wormhole.parseVM(encodedVm);
+        require(vm.emitterChainId == SOLANA_CHAIN_ID, "Transfer is not from Solana");
+
+        // Reverts unless the VAA is valid, unredeemed and addressed to this contract
+        ITokenBridge.TransferWithPayload memory transfer = _tokenBridge.parseTransferWithPayload(
+            _tokenBridge.completeTransferWithPayload(encodedVm)
+        );
+        require(transfer.fromAddress == _solanaProgram, "Invalid transfer sender");
+
+        bytes memory memo = transfer.payload;
+        // code, version, toChain, sender, senderChain, receiver, requestSequence, purpose
+        require(memo.length == 109, "Invalid payload length");
+        require(uint8(memo[0]) == TRANSFER_MEMO, "Unknown message type");
+        require(uint8(memo[1]) == PAYLOAD_VERSION, "Unsupported payload version");
+        require(_readUint256(memo, 2) == _wormhole.chainId(), "Message is not for this chain");
+
+        address token;
+        if (transfer.tokenChain == _tokenBridge.chainId()) {
+            token = address(uint160(uint256(transfer.tokenAddress)));
+        } else {
+            token = _tokenBridge.wrappedAsset(transfer.tokenChain, transfer.tokenAddress);
+        }
+        (, bytes memory queriedDecimals) = token.staticcall(abi.encodeWithSignature("decimals()"));
+        uint256 amount = deNormalizeAmount(transfer.amount, abi.decode(queriedDecimals, (uint8)));
+
+        address receiver = address(uint160(uint256(_readBytes32(memo, 68))));
+        require(IERC20(token).transfer(receiver, amount), "Token transfer failed");
+
+        emit TransferReceived(
+            _readUint64(memo, 100),
+            _readBytes32(memo, 34),
+            _readUint16(memo, 66),
+            receiver,
+            token,
+            amount,
+            uint8(memo[108])
+        );
+    }
+
+    function _readBytes32(bytes memory data, uint256 offset) internal pure returns (bytes32 value) {
+        require(data.length >= offset + 32, "Read out of bounds");
+        assembly {
+            value := mload(add(add(data, 32), offset))
+        }
+    }
+
+    function _readUint256(bytes memory data, uint256 offset) internal pure returns (uint256) {
+        return uint256(_readBytes32(data, offset));
+    }
+
+    function _readUint64(bytes memory data, uint256 offset) internal pure returns (uint64 value) {
+        require(data.length >= offset + 8, "Read out of bounds");
+        assembly {
+            value := and(mload(add(add(data, 8), offset)), 0xffffffffffffffff)
+        }
+    }
+
+    function _readUint16(bytes memory data, uint256 offset) internal pure returns (uint16 value) {
+        require(data.length >= offset + 2, "Read out of bounds");
+        assembly {
+            value := and(mload(add(add(data, 2), offset)), 0xffff)
+        }
+    }
+
+    function _readUint32(bytes memory data, uint256 offset) internal pure returns (uint32 value) {
+        require(data.length >= offset + 4, "Read out of bounds");
+        assembly {
+            value := and(mload(add(add(data, 4), offset)), 0xffffffff)
+        }
+    }
+
     function _bridgeInstructionInWormhole(uint32 nonceValue, bytes memory stream, uint256 arbiterFee) internal returns(uint64 sequence){
 
         uint256 wormholeFee = _wormhole.messageFee();
@@ -372,6 +805,12 @@ contract Messenger is Encoder {
         SOLANA_CHAIN_ID = _id;
     }
 
+    function setTokenBridge(address tokenBridge, bytes32 solanaProgram) public {
+        require(msg.sender == owner, "Only owner can set the token bridge!");
+        _tokenBridge = ITokenBridge(tokenBridge);
+        _solanaProgram = solanaProgram;
+    }
+
     function changeAdmin(address _owner) public {
         require(msg.sender == owner, "Only owner can change admin!");
         owner = _owner;
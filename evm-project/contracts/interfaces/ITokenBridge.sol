@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache 2
+
+pragma solidity ^0.8.0;
+
+interface ITokenBridge {
+    struct TransferWithPayload {
+        // PayloadID uint8 = 3
+        uint8 payloadID;
+        // Amount being transferred (big-endian uint256)
+        uint256 amount;
+        // Address of the token. Left-zero-padded if shorter than 32 bytes
+        bytes32 tokenAddress;
+        // Chain ID of the token
+        uint16 tokenChain;
+        // Address of the recipient. Left-zero-padded if shorter than 32 bytes
+        bytes32 to;
+        // Chain ID of the recipient
+        uint16 toChain;
+        // Address of the message sender. Left-zero-padded if shorter than 32 bytes
+        bytes32 fromAddress;
+        // An arbitrary payload
+        bytes payload;
+    }
+
+    function completeTransferWithPayload(bytes memory encodedVm) external returns (bytes memory);
+
+    function parseTransferWithPayload(bytes memory encoded) external pure returns (TransferWithPayload memory transfer);
+
+    function wrappedAsset(uint16 tokenChainId, bytes32 tokenAddress) external view returns (address);
+
+    function chainId() external view returns (uint16);
+}
@@ -23,6 +23,8 @@ const messengerAddress = fs.readFileSync("eth-address.txt").toString();
 const startTime = 1;
 const endTime = 2;
 const amount = 5000000000;
+// decimals `amount` is expressed in, scaled to the mint decimals on Solana
+const decimals = 9;
 const receiver = "0xD8BeCE69d19837947b8d5963E505aed51C6F53Fa";
 
 let tokenMint = fs
@@ -77,6 +79,8 @@ export async function sendMsg() {
             mintUint8,
             tryNativeToUint8Array(receiver, CHAIN_ID),
             BigNumber.from("10"),
+            CHAIN_ID,
+            decimals,
             {
                 gasLimit: BigNumber.from("10000000"),
                 value: BigNumber.from("100"),
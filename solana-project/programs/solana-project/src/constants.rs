@@ -1,7 +1,79 @@
-// pub const CORE_BRIDGE_ADDRESS: &str = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth";
-// pub const TOKEN_BRIDGE_ADDRESS: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+// Exactly one cluster feature picks the Wormhole deployment the program is built against
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "devnet",
+    feature = "testnet",
+    feature = "localnet"
+)))]
+compile_error!("enable one of the `mainnet`, `devnet`, `testnet` or `localnet` features");
 
-pub const EVM_CHAIN_ADDRESS_LENGTH: usize = 64;
+#[cfg(any(
+    all(
+        feature = "mainnet",
+        any(feature = "devnet", feature = "testnet", feature = "localnet")
+    ),
+    all(feature = "devnet", any(feature = "testnet", feature = "localnet")),
+    all(feature = "testnet", feature = "localnet")
+))]
+compile_error!(
+    "only one of the `mainnet`, `devnet`, `testnet` or `localnet` features can be enabled"
+);
 
+#[cfg(feature = "mainnet")]
+pub const CORE_BRIDGE_ADDRESS: &str = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth";
+#[cfg(feature = "mainnet")]
+pub const TOKEN_BRIDGE_ADDRESS: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+
+// Wormhole has no deployment on the Solana testnet cluster, its testnet guardians watch devnet
+#[cfg(any(feature = "devnet", feature = "testnet"))]
 pub const CORE_BRIDGE_ADDRESS: &str = "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5";
+#[cfg(any(feature = "devnet", feature = "testnet"))]
 pub const TOKEN_BRIDGE_ADDRESS: &str = "DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe";
+
+// Addresses of the Wormhole local validator (tilt) deployment
+#[cfg(feature = "localnet")]
+pub const CORE_BRIDGE_ADDRESS: &str = "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o";
+#[cfg(feature = "localnet")]
+pub const TOKEN_BRIDGE_ADDRESS: &str = "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE";
+
+// Wormhole chain id of Solana, inbound payloads must be addressed to it. Wormhole uses the same
+// chain ids on every network, so this does not depend on the cluster feature.
+pub const LOCAL_CHAIN_ID: u16 = 1;
+
+// EVM addresses are 20 bytes, left-padded with zeros to 32 in Wormhole messages
+pub const EVM_ADDRESS_LENGTH: usize = 20;
+
+pub const MAX_RELAYERS: usize = 10;
+
+// Layout version of Config, migrate_config moves older accounts to it
+pub const CONFIG_VERSION: u8 = 1;
+
+// Decimals of SOL, native payload amounts are scaled from their own decimals to lamports
+pub const SOL_DECIMALS: u8 = 9;
+
+// Streams in a batch VAA, each one takes two remaining accounts of the transaction
+pub const MAX_BATCH_SIZE: usize = 8;
+
+// Lamports above rent exemption given to a new sender PDA, and refill trigger, until set_pda_funding
+pub const DEFAULT_PDA_FUNDING: u64 = 5_000_000;
+pub const DEFAULT_PDA_TOP_UP_THRESHOLD: u64 = 1_000_000;
+
+// Sequences tracked by one SequenceBitmap account
+pub const SEQUENCES_PER_BITMAP: u64 = 1024;
+
+// Seconds a ProcessedVAA is kept before its rent can be reclaimed, until set_vaa_retention
+pub const DEFAULT_VAA_RETENTION: i64 = 30 * 24 * 60 * 60;
+// Longest retention set_vaa_retention accepts
+pub const MAX_VAA_RETENTION: i64 = 365 * 24 * 60 * 60;
+
+// Size of a zebec StreamToken account, discriminator included
+pub const STREAM_TOKEN_SIZE: usize = 8 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 32 + 8 + 8 + 32 + 8 + 1 + 1;
+
+// Size of a zebec Stream account, the native one, discriminator included
+pub const STREAM_SIZE: usize = 8 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 32 + 8 + 1 + 1;
+
+// token_mint reported back for native SOL streams
+pub const NATIVE_MINT_BYTES: [u8; 32] = [0; 32];
+
+// Token Bridge transfers carry at most 8 decimals, it truncates amounts of mints with more
+pub const TOKEN_BRIDGE_MAX_DECIMALS: u8 = 8;
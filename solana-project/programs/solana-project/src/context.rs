@@ -4,12 +4,11 @@ use anchor_spl::{
     token::{Mint, TokenAccount, Token}
 };
 use crate::constants::*;
+use crate::errors::MessengerError;
 use crate::portal::TokenPortalBridge;
 use crate::state::*;
 use std::str::FromStr;
 use crate::wormhole::*;
-use hex::decode;
-use zebec::{ StreamToken};
 use zebec::program::Zebec;
 
 #[derive(Accounts)]
@@ -19,7 +18,7 @@ pub struct Initialize<'info> {
         seeds=[b"config".as_ref()],
         payer=owner,
         bump,
-        space=8+32+4
+        space=Config::LEN
     )]
     pub config: Account<'info, Config>,
     #[account(mut)]
@@ -28,21 +27,309 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(chain_id:u16, emitter_addr:String)]
-pub struct RegisterChain<'info> {
+pub struct MigrateConfig<'info> {
+    #[account(
+        mut,
+        seeds=[b"config".as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: still in the legacy layout, read and rewritten in the function itself
+    pub config: UncheckedAccount<'info>,
     #[account(mut)]
     pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub owner: Signer<'info>,
     #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
         constraint = config.owner == owner.key()
     )]
     pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    pub pending_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.pending_owner == pending_owner.key() @ MessengerError::NotPendingOwner
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub pauser: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.pauser == pauser.key()
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32])]
+pub struct SetEmitterPaused<'info> {
+    pub pauser: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.pauser == pauser.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref()
+        ],
+        bump,
+    )]
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32])]
+pub struct RegisterChain<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
     #[account(
         init,
-        seeds=[b"EmitterAddress".as_ref(), chain_id.to_be_bytes().as_ref()],
-        payer=owner,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref()
+        ],
+        payer=admin,
+        bump,
+        space=8 + 2 + 32 + 1 + 1 + 1 + 1 + 4
+    )]
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32])]
+pub struct MigrateEmitter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref()
+        ],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: still in the legacy layout, read and closed in the function itself
+    pub legacy_emitter_acc: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref()
+        ],
+        payer=admin,
+        bump,
+        space=8 + 2 + 32 + 1 + 1 + 1 + 1 + 4
+    )]
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32])]
+pub struct ManageEmitter<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref()
+        ],
+        bump,
+    )]
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32], sequence:u64)]
+pub struct CloseProcessedVAA<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&authority.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds=[
+            b"sequences".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref(),
+            (sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    #[account(
+        mut,
+        close = relayer,
+        has_one = relayer,
+        seeds=[
+            emitter_addr.as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(mut)]
+    /// CHECK: gets the rent back, checked against processed_vaa
+    pub relayer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32], sequence:u64)]
+pub struct CloseLegacyProcessedVAA<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer=admin,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref(),
+            (sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    #[account(
+        mut,
+        seeds=[
+            emitter_addr.as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: still in the legacy layout, read and closed in the function itself
+    pub processed_vaa: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: picked by the admin, the legacy layout does not record who paid for the account
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16)]
+pub struct SetFeeSchedule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        seeds=[
+            b"fee_schedule".as_ref(),
+            chain_id.to_be_bytes().as_ref()
+        ],
+        payer=admin,
+        bump,
+        space=FeeSchedule::LEN
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32])]
+pub struct DisableEmitter<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == authority.key() || config.pauser == authority.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref()
+        ],
+        bump,
+    )]
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id:u16, emitter_addr:[u8;32])]
+pub struct RemoveEmitter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds=[
+            b"EmitterAddress".as_ref(),
+            chain_id.to_be_bytes().as_ref(),
+            emitter_addr.as_ref()
+        ],
         bump,
-        space=8 + 2 + 4 + EVM_CHAIN_ADDRESS_LENGTH
     )]
     pub emitter_acc: Account<'info, EmitterAddrAccount>,
 }
@@ -57,9 +344,9 @@ pub struct InitializePDA<'info> {
     #[account(
         init,
         payer=zebec_eoa,
-        space=8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
@@ -67,7 +354,26 @@ pub struct InitializePDA<'info> {
         
     )]
     pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
     pub emitter_acc: Account<'info, EmitterAddrAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Account<'info, Config>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
@@ -85,6 +391,46 @@ pub struct InitializePDA<'info> {
     )]
     /// CHECK:: pda_account are checked inside
     pub pda_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=PdaSponsorship::LEN,
+        seeds=[b"sponsorship".as_ref(), pda_account.key().as_ref()],
+        bump,
+    )]
+    pub pda_sponsorship: Account<'info, PdaSponsorship>,
+}
+
+#[derive(Accounts)]
+#[instruction(sender:[u8;32], chain_id:u16)]
+pub struct TopUpPDA<'info> {
+    #[account(mut)]
+    pub zebec_eoa: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: sender PDA, seeds checked
+    pub pda_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=PdaSponsorship::LEN,
+        seeds=[b"sponsorship".as_ref(), pda_account.key().as_ref()],
+        bump,
+    )]
+    pub pda_sponsorship: Account<'info, PdaSponsorship>,
 }
 
 #[derive(Accounts)]
@@ -100,16 +446,35 @@ pub struct InitializePDATokenAccount<'info> {
     #[account(
         init,
         payer=zebec_eoa,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
     pub emitter_acc: Account<'info, EmitterAddrAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Account<'info, Config>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
@@ -139,6 +504,7 @@ pub struct InitializePDATokenAccount<'info> {
 #[instruction( 
     sender: [u8; 32],
     chain_id: u16,
+    target_chain: u16,
 )]
 pub struct XstreamDirectTransferNative<'info> {
     // ZEBEC's EOA.
@@ -147,15 +513,28 @@ pub struct XstreamDirectTransferNative<'info> {
     #[account(
         init,
         payer=zebec_eoa,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
     pub emitter_acc: Account<'info, EmitterAddrAccount>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
@@ -175,11 +554,22 @@ pub struct XstreamDirectTransferNative<'info> {
     )]
     pub pda_signer: UncheckedAccount<'info>,
 
-    //Native Transfer
+    #[account(
+        seeds = [
+            b"fee_schedule".as_ref(),
+            target_chain.to_be_bytes().as_ref()
+        ],
+        bump
+    )]
+    /// CHECK: FeeSchedule of the target chain, left uninitialized when the chain has none
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    //Native Transfer
     #[account(
         mut,
         seeds = [b"config"],
         bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
     )]
     pub config: Account<'info, Config>,
 
@@ -200,8 +590,7 @@ pub struct XstreamDirectTransferNative<'info> {
     pub from: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    /// CHECK: No need of data
-    pub mint: AccountInfo<'info>,
+    pub mint: Box<Account<'info, Mint>>,
 
     #[account(
         mut,
@@ -291,6 +680,7 @@ pub struct XstreamDirectTransferNative<'info> {
     sender_chain: u16,
     _token_address: Vec<u8>,
     _token_chain: u16,
+    target_chain: u16,
 )]
 pub struct XstreamDirectTransferWrapped<'info> {
     
@@ -300,15 +690,28 @@ pub struct XstreamDirectTransferWrapped<'info> {
     #[account(
         init,
         payer=zebec_eoa,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
     pub emitter_acc: Account<'info, EmitterAddrAccount>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
@@ -328,11 +731,22 @@ pub struct XstreamDirectTransferWrapped<'info> {
     )]
     pub pda_signer: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [
+            b"fee_schedule".as_ref(),
+            target_chain.to_be_bytes().as_ref()
+        ],
+        bump
+    )]
+    /// CHECK: FeeSchedule of the target chain, left uninitialized when the chain has none
+    pub fee_schedule: UncheckedAccount<'info>,
+
     //Wrapped Transfer
     #[account(
         mut,
         seeds = [b"config"],
         bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
     )]
     pub config: Account<'info, Config>,
         
@@ -362,8 +776,7 @@ pub struct XstreamDirectTransferWrapped<'info> {
         seeds::program = portal_bridge_program.key(),
         bump,
     )]
-    /// CHECK: portal config
-    pub wrapped_mint: AccountInfo<'info>,
+    pub wrapped_mint: Box<Account<'info, Mint>>,
 
     #[account(
         mut,
@@ -442,154 +855,241 @@ pub struct XstreamDirectTransferWrapped<'info> {
 
 }
 
-// Single Transaction Contexts
 #[derive(Accounts)]
-#[instruction(
-    sender:[u8;32],
-    from_chain_id: u16,
+#[instruction( 
+    sender: [u8; 32],
+    chain_id: u16,
+    target_chain: u16,
 )]
-pub struct XstreamStart<'info> {
+pub struct XstreamDirectTransferNativeWithPayload<'info> {
     // ZEBEC's EOA.
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub zebec_eoa: Signer<'info>,
     #[account(
         init,
-        payer=payer,
-        space= 8 + 8,
+        payer=zebec_eoa,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
-    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
-    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
     )]
     /// CHECK: This account is owned by Core Bridge so we trust it
     pub core_bridge_vaa: AccountInfo<'info>,
-    #[account(zero)]
-    pub data_account:  Account<'info, StreamToken>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub withdraw_data: AccountInfo<'info>,
-    /// CHECK: validated in fee_vault constraint
-    pub fee_owner:AccountInfo<'info>,
-    /// CHECK: This will be validated on zebec core contract
-    pub fee_vault_data: AccountInfo<'info>,
-    /// CHECK: This will be validated on zebec core contract
-    pub fee_vault:AccountInfo<'info>,
+
+    /// Emitter of the Messenger on the target chain, which redeems the transfer
+    #[account(
+        constraint = target_emitter_acc.chain_id == target_chain @ MessengerError::TargetEmitterMismatch
+    )]
+    pub target_emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+
+    ///CHECK: pda seeds checked
     #[account(
         mut,
         seeds = [
             &sender,
-            &from_chain_id.to_be_bytes()
-            ],
-            bump
-        )]
-    /// CHECK: seeds has been checked
-    pub source_account: UncheckedAccount<'info>,
-    /// CHECK: new stream receiver, do not need to be checked
-    pub dest_account: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program:Program<'info,Token>,
-    pub mint:Account<'info,Mint>,
-    pub rent: Sysvar<'info, Rent>,
-    pub zebec_program: Program<'info, Zebec>,
+            &chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    pub pda_signer: UncheckedAccount<'info>,
+
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = dest_account,
+        seeds = [
+            b"fee_schedule".as_ref(),
+            target_chain.to_be_bytes().as_ref()
+        ],
+        bump
     )]
-    pub dest_token_account: Box<Account<'info, TokenAccount>>,
-    pub associated_token_program:Program<'info,AssociatedToken>,
+    /// CHECK: FeeSchedule of the target chain, left uninitialized when the chain has none
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    //Native Transfer
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = fee_vault,
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
     )]
-    fee_receiver_token_account: Box<Account<'info, TokenAccount>>,
-}
+    pub config: Account<'info, Config>,
 
-#[derive(Accounts)]
-#[instruction(
-    sender:[u8;32],
-    from_chain_id: u16,
-)]
-pub struct XstreamUpdate<'info> {
-    // ZEBEC's EOA.
-    #[account(mut)]
-    pub payer: Signer<'info>,
     #[account(
-        init,
-        payer=payer,
-        space= 8 + 8,
-        seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            emitter_acc.chain_id.to_be_bytes().as_ref(),
-            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
-        ],
+        mut,
+        seeds = [b"config"],
+        seeds::program = portal_bridge_program.key(),
         bump,
     )]
-    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
-    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
-    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    /// CHECK: portal config
+    pub portal_config: AccountInfo<'info>,
+    
     #[account(
-        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+        mut,
+        constraint = from.owner == pda_signer.key(),
+        constraint = from.mint == mint.key(),
     )]
-    /// CHECK: This account is owned by Core Bridge so we trust it
-    pub core_bridge_vaa: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub data_account:  AccountInfo<'info>,
+    pub from: Box<Account<'info, TokenAccount>>,
+
+    /// Token account of the relayer, paid the relayer fee on Solana as the Token Bridge does not
+    /// carry one for transfers with payload
+    #[account(
+        mut,
+        constraint = relayer_token_account.owner == zebec_eoa.key(),
+        constraint = relayer_token_account.mint == mint.key(),
+    )]
+    pub relayer_token_account: Box<Account<'info, TokenAccount>>,
+
     #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub withdraw_data: AccountInfo<'info>,
+    pub mint: Box<Account<'info, Mint>>,
+
     #[account(
         mut,
-        seeds = [
-            &sender,
-            &from_chain_id.to_be_bytes()
-        ],
+        seeds = [mint.key().as_ref()],
+        seeds::program = portal_bridge_program.key(),
         bump
     )]
-    /// CHECK: seeds has been checked
-    pub source_account: UncheckedAccount<'info>,
-    /// CHECK: stream receiver checked in data account
-    pub dest_account: AccountInfo<'info>,
-    pub mint:Account<'info,Mint>,
+    /// CHECK: portal custody
+    pub portal_custody: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"authority_signer"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal authority signer
+    pub portal_authority_signer: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"custody_signer"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal custody signer
+    pub portal_custody_signer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Bridge"],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: bridge config
+    pub bridge_config: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        signer
+    )]
+    /// CHECK: portal message
+    pub portal_message: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"emitter"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal emitter
+    pub portal_emitter: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"Sequence", portal_emitter.key().as_ref()],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal sequence
+    pub portal_sequence: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: bridge fee collector
+    pub bridge_fee_collector: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"sender"],
+        bump
+    )]
+    /// CHECK: Token Bridge sender of transfers with payload, seeds checked
+    pub program_sender: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub rent: Sysvar<'info, Rent>,
+
     pub system_program: Program<'info, System>,
-    pub zebec_program: Program<'info, Zebec>
+
+    pub portal_bridge_program: Program<'info, TokenPortalBridge>,
+
+    pub core_bridge_program: Program<'info, WormholeCoreBridge>,
+
+    pub token_program: Program<'info, Token>
 
 }
 
 #[derive(Accounts)]
-#[instruction(
-    sender:[u8;32],
-    from_chain_id: u16,
+#[instruction( 
+    sender: [u8; 32],
+    sender_chain: u16,
+    _token_address: Vec<u8>,
+    _token_chain: u16,
+    target_chain: u16,
 )]
-pub struct XstreamDeposit<'info> {
+pub struct XstreamDirectTransferWrappedWithPayload<'info> {
+    
     // ZEBEC's EOA.
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub zebec_eoa: Signer<'info>,
     #[account(
         init,
-        payer=payer,
-        space= 8 + 8,
+        payer=zebec_eoa,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
-    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
-    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=zebec_eoa,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
@@ -597,6 +1097,1096 @@ pub struct XstreamDeposit<'info> {
     /// CHECK: This account is owned by Core Bridge so we trust it
     pub core_bridge_vaa: AccountInfo<'info>,
 
+    /// Emitter of the Messenger on the target chain, which redeems the transfer
+    #[account(
+        constraint = target_emitter_acc.chain_id == target_chain @ MessengerError::TargetEmitterMismatch
+    )]
+    pub target_emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+
+    ///CHECK: pda seeds checked
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &sender_chain.to_be_bytes()
+        ],
+        bump
+    )]
+    pub pda_signer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"fee_schedule".as_ref(),
+            target_chain.to_be_bytes().as_ref()
+        ],
+        bump
+    )]
+    /// CHECK: FeeSchedule of the target chain, left uninitialized when the chain has none
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    //Wrapped Transfer
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&zebec_eoa.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Account<'info, Config>,
+        
+    #[account(
+        mut,
+        constraint = from.owner == pda_signer.key(),
+        constraint = from.mint == wrapped_mint.key(),
+    )]
+    pub from: Box<Account<'info, TokenAccount>>,
+
+    /// Token account of the relayer, paid the relayer fee on Solana as the Token Bridge does not
+    /// carry one for transfers with payload
+    #[account(
+        mut,
+        constraint = relayer_token_account.owner == zebec_eoa.key(),
+        constraint = relayer_token_account.mint == wrapped_mint.key(),
+    )]
+    pub relayer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        seeds::program = portal_bridge_program.key(),
+        bump,
+    )]
+    /// CHECK: portal config
+    pub portal_config: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"wrapped",
+            _token_chain.to_be_bytes().as_ref(),
+            _token_address.as_ref()
+        ],
+        seeds::program = portal_bridge_program.key(),
+        bump,
+    )]
+    pub wrapped_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"meta",
+            wrapped_mint.key().as_ref()
+        ],
+        seeds::program = portal_bridge_program.key(),
+        bump,
+    )]
+    /// CHECK: portal config
+    pub wrapped_meta: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"authority_signer"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal authority signer
+    pub portal_authority_signer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Bridge"],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: bridge config
+    pub bridge_config: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        signer
+    )]
+    /// CHECK: portal message
+    pub portal_message: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"emitter"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal emitter
+    pub portal_emitter: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Sequence", portal_emitter.key().as_ref()],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal sequence
+    pub portal_sequence: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: bridge fee collector
+    pub bridge_fee_collector: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"sender"],
+        bump
+    )]
+    /// CHECK: Token Bridge sender of transfers with payload, seeds checked
+    pub program_sender: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub portal_bridge_program: Program<'info, TokenPortalBridge>,
+
+    pub core_bridge_program: Program<'info, WormholeCoreBridge>,
+
+    pub token_program: Program<'info, Token>
+
+}
+
+// Single Transaction Contexts
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamStart<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: derived from the payload stream id and created in the function itself
+    pub data_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    /// CHECK: validated in fee_vault constraint
+    pub fee_owner:AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault_data: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault:AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+            ],
+            bump
+        )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: new stream receiver, do not need to be checked
+    pub dest_account: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub mint:Account<'info,Mint>,
+    pub rent: Sysvar<'info, Rent>,
+    pub zebec_program: Program<'info, Zebec>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = dest_account,
+    )]
+    pub dest_token_account: Box<Account<'info, TokenAccount>>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = fee_vault,
+    )]
+    fee_receiver_token_account: Box<Account<'info, TokenAccount>>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+// Remaining accounts: the receiver PDA and stream data account of every stream, in batch order
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamBatchStart<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    /// CHECK: validated in fee_vault constraint
+    pub fee_owner:AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault_data: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault:AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+            ],
+            bump
+        )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub mint:Account<'info,Mint>,
+    pub rent: Sysvar<'info, Rent>,
+    pub zebec_program: Program<'info, Zebec>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = fee_vault,
+    )]
+    fee_receiver_token_account: Box<Account<'info, TokenAccount>>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamUpdate<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub data_account:  AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: stream receiver checked in data account
+    pub dest_account: AccountInfo<'info>,
+    pub mint:Account<'info,Mint>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamDeposit<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub zebec_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub mint:Account<'info,Mint>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub source_account_token_account:AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub pda_account_token_account:AccountInfo<'info>,
+    pub zebec_program: Program<'info, Zebec>
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16
+)]
+pub struct XstreamSenderWithdraw<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub zebec_vault: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub mint:Account<'info,Mint>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub source_account_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub pda_account_token_account: AccountInfo<'info>,
+    pub zebec_program: Program<'info, Zebec>
+
+}
+
+#[derive(Accounts)]
+#[instruction(
+    eth_add:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamWithdraw<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    ///CHECK: seeds are checked
+    #[account(
+        mut,
+        seeds = [
+            &eth_add,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    pub dest_account: UncheckedAccount<'info>,
+    /// CHECK: seeds has been checked
+    pub zebec_vault: AccountInfo<'info>,
+    // #[account(mut)]
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub source_account: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_owner:AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault_data: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault:AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub data_account:  AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub mint:Account<'info,Mint>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub pda_account_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub dest_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_receiver_token_account: AccountInfo<'info>,
+    pub zebec_program: Program<'info, Zebec>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamPause<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: validated in data_account constraint
+    pub dest_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub data_account: AccountInfo<'info>,
+    pub mint:Account<'info,Mint>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>
+    
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamCancel<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: seeds has been checked
+    pub zebec_vault: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: validated in data_account constraint
+    pub dest_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: validated in fee_vault constraint
+    pub fee_owner:AccountInfo<'info>, 
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault_data:AccountInfo<'info>, 
+    /// CHECK: seeds has been checked
+    pub fee_vault:AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub data_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub mint:Account<'info,Mint>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub pda_account_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub dest_token_account:AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_receiver_token_account: AccountInfo<'info>,
+    pub zebec_program: Program<'info, Zebec>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamInstant<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    /// CHECK: seeds has been checked
+    pub zebec_vault: AccountInfo<'info>,
+    /// CHECK: This is the receiver account, since the funds are transferred directly, we do not need to check it
+    #[account(mut)]
+    pub dest_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program:Program<'info,Token>,
+    pub associated_token_program:Program<'info,AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub mint:Account<'info,Mint>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub pda_account_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub dest_token_account: AccountInfo<'info>,
+    pub zebec_program: Program<'info, Zebec>
+}
+
+// Native SOL Contexts, the sender PDA funds the stream with its own lamports
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamNativeStart<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: derived from the payload stream id and created in the function itself
+    pub data_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    /// CHECK: validated in fee_vault constraint
+    pub fee_owner:AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault_data: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault:AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: new stream receiver, do not need to be checked
+    pub dest_account: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamNativeUpdate<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub data_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: stream receiver checked in data account
+    pub dest_account: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamNativeDeposit<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
     #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
     pub zebec_vault: AccountInfo<'info>,
@@ -611,47 +2201,57 @@ pub struct XstreamDeposit<'info> {
     /// CHECK: seeds has been checked
     pub source_account: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program:Program<'info,Token>,
-    pub associated_token_program:Program<'info,AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    pub mint:Account<'info,Mint>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub source_account_token_account:AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub pda_account_token_account:AccountInfo<'info>,
     pub zebec_program: Program<'info, Zebec>
-
 }
 
 #[derive(Accounts)]
 #[instruction(
     sender:[u8;32],
-    from_chain_id: u16
+    from_chain_id: u16,
 )]
-pub struct XstreamSenderWithdraw<'info> {
+pub struct XstreamNativeSenderWithdraw<'info> {
     // ZEBEC's EOA.
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
         init,
         payer=payer,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
     pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
     )]
     /// CHECK: This account is owned by Core Bridge so we trust it
     pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
     pub zebec_vault: AccountInfo<'info>,
     #[account(mut)]
@@ -668,18 +2268,7 @@ pub struct XstreamSenderWithdraw<'info> {
     /// CHECK: seeds has been checked
     pub source_account: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program:Program<'info,Token>,
-    pub associated_token_program:Program<'info,AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    pub mint:Account<'info,Mint>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub source_account_token_account: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub pda_account_token_account: AccountInfo<'info>,
     pub zebec_program: Program<'info, Zebec>
-
 }
 
 #[derive(Accounts)]
@@ -687,22 +2276,43 @@ pub struct XstreamSenderWithdraw<'info> {
     eth_add:[u8;32],
     from_chain_id: u16,
 )]
-pub struct XstreamWithdraw<'info> {
+pub struct XstreamNativeWithdraw<'info> {
+    // ZEBEC's EOA.
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
         init,
         payer=payer,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
     pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
@@ -719,13 +2329,160 @@ pub struct XstreamWithdraw<'info> {
         bump
     )]
     pub dest_account: UncheckedAccount<'info>,
+    #[account(mut)]
     /// CHECK: seeds has been checked
     pub zebec_vault: AccountInfo<'info>,
-    // #[account(mut)]
     #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
     pub source_account: AccountInfo<'info>,
+    /// CHECK: validated in fee_vault constraint
+    pub fee_owner:AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault_data: AccountInfo<'info>,
+    /// CHECK: This will be validated on zebec core contract
+    pub fee_vault:AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub data_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamNativePause<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: validated in data_account constraint
+    pub dest_account: AccountInfo<'info>,
+    #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
+    pub data_account: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamNativeCancel<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: seeds has been checked
+    pub zebec_vault: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: validated in data_account constraint
+    pub dest_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
+    /// CHECK: validated in fee_vault constraint
     pub fee_owner:AccountInfo<'info>,
     /// CHECK: This will be validated on zebec core contract
     pub fee_vault_data: AccountInfo<'info>,
@@ -733,49 +2490,130 @@ pub struct XstreamWithdraw<'info> {
     pub fee_vault:AccountInfo<'info>,
     #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
-    pub data_account:  AccountInfo<'info>,
+    pub data_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: This will be validated on zebec core contract
+    pub withdraw_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub zebec_program: Program<'info, Zebec>,
+    pub outbound: WormholeOutbound<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender:[u8;32],
+    from_chain_id: u16,
+)]
+pub struct XstreamNativeInstant<'info> {
+    // ZEBEC's EOA.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer=payer,
+        space=ProcessedVAA::LEN,
+        seeds=[
+            emitter_acc.emitter_addr.as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// This requires some fancy hashing, so confirm it's derived address in the function itself.
+    #[account(
+        constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
+    )]
+    /// CHECK: This account is owned by Core Bridge so we trust it
+    pub core_bridge_vaa: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: seeds has been checked
+    pub zebec_vault: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: validated against the payload receiver
+    pub dest_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &sender,
+            &from_chain_id.to_be_bytes()
+        ],
+        bump
+    )]
+    /// CHECK: seeds has been checked
+    pub source_account: UncheckedAccount<'info>,
     #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
     pub withdraw_data: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program:Program<'info,Token>,
-    pub associated_token_program:Program<'info,AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    pub mint:Account<'info,Mint>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub pda_account_token_account: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub dest_token_account: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub fee_receiver_token_account: AccountInfo<'info>,
     pub zebec_program: Program<'info, Zebec>
 }
 
+// Bridge-in Contexts, redeem a Token Bridge transfer with payload and fund zebec with it
 #[derive(Accounts)]
 #[instruction(
     sender:[u8;32],
     from_chain_id: u16,
 )]
-pub struct XstreamPause<'info> {
+pub struct XstreamBridgeInNative<'info> {
     // ZEBEC's EOA.
     #[account(mut)]
     pub payer: Signer<'info>,
+    // Receipt of the Token Bridge VAA, keyed by the Token Bridge emitter
     #[account(
         init,
         payer=payer,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_address.as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_chain.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_chain.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_address.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    // Registered Messenger that sent the transfer
     pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
@@ -790,20 +2628,65 @@ pub struct XstreamPause<'info> {
         ],
         bump
     )]
-    /// CHECK: seeds has been checked
+    /// CHECK: seeds has been checked, redeemer of the transfer
     pub source_account: UncheckedAccount<'info>,
-    /// CHECK: validated in data_account constraint
-    pub dest_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = source_account_token_account.owner == source_account.key(),
+        constraint = source_account_token_account.mint == mint.key(),
+    )]
+    pub source_account_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"config"],
+        seeds::program = portal_bridge_program.key(),
+        bump,
+    )]
+    /// CHECK: portal config
+    pub portal_config: AccountInfo<'info>,
+
     #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub data_account: AccountInfo<'info>,
-    pub mint:Account<'info,Mint>,
+    /// CHECK: portal claim, validated by Token Bridge
+    pub portal_claim: AccountInfo<'info>,
+
+    /// CHECK: registered Token Bridge of the source chain, validated by Token Bridge
+    pub portal_endpoint: AccountInfo<'info>,
+
     #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub withdraw_data: AccountInfo<'info>,
+    /// CHECK: receives the relayer fee of the transfer, validated by Token Bridge
+    pub portal_fee_recipient: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [mint.key().as_ref()],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal custody
+    pub portal_custody: AccountInfo<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"custody_signer"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal custody signer
+    pub portal_custody_signer: AccountInfo<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
     pub system_program: Program<'info, System>,
-    pub zebec_program: Program<'info, Zebec>
-    
+
+    pub portal_bridge_program: Program<'info, TokenPortalBridge>,
+
+    pub core_bridge_program: Program<'info, WormholeCoreBridge>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub zebec: BridgeInZebec<'info>,
+    pub outbound: WormholeOutbound<'info>,
 }
 
 #[derive(Accounts)]
@@ -811,35 +2694,51 @@ pub struct XstreamPause<'info> {
     sender:[u8;32],
     from_chain_id: u16,
 )]
-pub struct XstreamCancel<'info> {
+pub struct XstreamBridgeInWrapped<'info> {
     // ZEBEC's EOA.
     #[account(mut)]
     pub payer: Signer<'info>,
+    // Receipt of the Token Bridge VAA, keyed by the Token Bridge emitter
     #[account(
         init,
         payer=payer,
-        space= 8 + 8,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_address.as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_chain.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
     pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_chain.to_be_bytes().as_ref(),
+            (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).emitter_address.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    // Registered Messenger that sent the transfer
     pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.is_relayer(&payer.key()) @ MessengerError::InvalidRelayer
+    )]
+    pub config: Box<Account<'info, Config>>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
     )]
     /// CHECK: This account is owned by Core Bridge so we trust it
     pub core_bridge_vaa: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: seeds has been checked
-    pub zebec_vault: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: validated in data_account constraint
-    pub dest_account: AccountInfo<'info>,
     #[account(
         mut,
         seeds = [
@@ -848,93 +2747,189 @@ pub struct XstreamCancel<'info> {
         ],
         bump
     )]
-    /// CHECK: seeds has been checked
+    /// CHECK: seeds has been checked, redeemer of the transfer
     pub source_account: UncheckedAccount<'info>,
-    /// CHECK: validated in fee_vault constraint
-    pub fee_owner:AccountInfo<'info>, 
+    #[account(
+        mut,
+        constraint = source_account_token_account.owner == source_account.key(),
+        constraint = source_account_token_account.mint == mint.key(),
+    )]
+    pub source_account_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"config"],
+        seeds::program = portal_bridge_program.key(),
+        bump,
+    )]
+    /// CHECK: portal config
+    pub portal_config: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: portal claim, validated by Token Bridge
+    pub portal_claim: AccountInfo<'info>,
+
+    /// CHECK: registered Token Bridge of the source chain, validated by Token Bridge
+    pub portal_endpoint: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: receives the relayer fee of the transfer, validated by Token Bridge
+    pub portal_fee_recipient: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"meta", mint.key().as_ref()],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: wrapped meta
+    pub wrapped_meta: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"mint_signer"],
+        seeds::program = portal_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: portal mint authority
+    pub portal_mint_authority: AccountInfo<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub portal_bridge_program: Program<'info, TokenPortalBridge>,
+
+    pub core_bridge_program: Program<'info, WormholeCoreBridge>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub zebec: BridgeInZebec<'info>,
+    pub outbound: WormholeOutbound<'info>,
+}
+
+// Zebec accounts used once bridged tokens have landed in the sender PDA's token account
+#[derive(Accounts)]
+pub struct BridgeInZebec<'info> {
+    #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
-    pub fee_vault_data:AccountInfo<'info>, 
-    /// CHECK: seeds has been checked
-    pub fee_vault:AccountInfo<'info>,
+    pub zebec_vault: AccountInfo<'info>,
     #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
+    pub pda_account_token_account: AccountInfo<'info>,
+    // The accounts below are only used by TokenStream messages
+    #[account(mut)]
+    /// CHECK: derived from the payload stream id and created in the function itself
     pub data_account: AccountInfo<'info>,
     #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
     pub withdraw_data: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program:Program<'info,Token>,
-    pub associated_token_program:Program<'info,AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    pub mint:Account<'info,Mint>,
-    #[account(mut)]
     /// CHECK: This will be validated on zebec core contract
-    pub pda_account_token_account: AccountInfo<'info>,
-    #[account(mut)]
+    pub fee_owner: AccountInfo<'info>,
     /// CHECK: This will be validated on zebec core contract
-    pub dest_token_account:AccountInfo<'info>,
-    #[account(mut)]
+    pub fee_vault_data: AccountInfo<'info>,
     /// CHECK: This will be validated on zebec core contract
-    pub fee_receiver_token_account: AccountInfo<'info>,
-    pub zebec_program: Program<'info, Zebec>
+    pub fee_vault: AccountInfo<'info>,
+    /// CHECK: checked against the stream receiver in the function itself
+    pub dest_account: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub zebec_program: Program<'info, Zebec>,
 }
 
 #[derive(Accounts)]
-#[instruction(
-    sender:[u8;32],
-    from_chain_id: u16,
-)]
-pub struct XstreamInstant<'info> {
-    // ZEBEC's EOA.
+pub struct ReportExecutionFailed<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
     #[account(
         init,
-        payer=payer,
-        space= 8 + 8,
+        payer=admin,
+        space=ProcessedVAA::LEN,
         seeds=[
-            &decode(&emitter_acc.emitter_addr.as_str()).unwrap()[..],
+            emitter_acc.emitter_addr.as_ref(),
             emitter_acc.chain_id.to_be_bytes().as_ref(),
             (PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence.to_be_bytes().as_ref()
         ],
         bump,
     )]
-    pub processed_vaa: Box<Account<'info, ProcessedVAA>>,
-    pub emitter_acc: Box<Account<'info, EmitterAddrAccount>>,
+    pub processed_vaa: Account<'info, ProcessedVAA>,
+    #[account(
+        init_if_needed,
+        payer=admin,
+        space=SequenceBitmap::LEN,
+        seeds=[
+            b"sequences".as_ref(),
+            emitter_acc.chain_id.to_be_bytes().as_ref(),
+            emitter_acc.emitter_addr.as_ref(),
+            ((PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0).sequence / SEQUENCES_PER_BITMAP).to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub sequence_bitmap: Box<Account<'info, SequenceBitmap>>,
+    pub emitter_acc: Account<'info, EmitterAddrAccount>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key()
+    )]
+    pub config: Account<'info, Config>,
     /// This requires some fancy hashing, so confirm it's derived address in the function itself.
     #[account(
         constraint = core_bridge_vaa.to_account_info().owner == &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
     )]
     /// CHECK: This account is owned by Core Bridge so we trust it
     pub core_bridge_vaa: AccountInfo<'info>,
-    /// CHECK: seeds has been checked
-    pub zebec_vault: AccountInfo<'info>,
-    /// CHECK: This is the receiver account, since the funds are transferred directly, we do not need to check it
-    #[account(mut)]
-    pub dest_account: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub outbound: WormholeOutbound<'info>,
+}
+
+// Core Bridge accounts needed to post a message from the program emitter
+#[derive(Accounts)]
+pub struct WormholeOutbound<'info> {
     #[account(
         mut,
-        seeds = [
-            &sender,
-            &from_chain_id.to_be_bytes()
-        ],
+        seeds = [b"Bridge"],
+        seeds::program = core_bridge_program.key(),
         bump
     )]
-    /// CHECK: seeds has been checked
-    pub source_account: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub withdraw_data: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program:Program<'info,Token>,
-    pub associated_token_program:Program<'info,AssociatedToken>,
+    /// CHECK: bridge config
+    pub bridge_config: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        signer
+    )]
+    /// CHECK: new message account, created by Core Bridge
+    pub wormhole_message: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"emitter"],
+        bump
+    )]
+    /// CHECK: program emitter, signs for the message
+    pub wormhole_emitter: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Sequence", wormhole_emitter.key().as_ref()],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: emitter sequence
+    pub wormhole_sequence: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        seeds::program = core_bridge_program.key(),
+        bump
+    )]
+    /// CHECK: bridge fee collector
+    pub bridge_fee_collector: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
     pub rent: Sysvar<'info, Rent>,
-    pub mint:Account<'info,Mint>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub pda_account_token_account: AccountInfo<'info>,
-    #[account(mut)]
-    /// CHECK: This will be validated on zebec core contract
-    pub dest_token_account: AccountInfo<'info>,
-    pub zebec_program: Program<'info, Zebec>
+
+    pub core_bridge_program: Program<'info, WormholeCoreBridge>,
 }
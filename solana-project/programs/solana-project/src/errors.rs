@@ -79,4 +79,100 @@ pub enum MessengerError {
 
     #[msg("Invalid Count")]
     InvalidCount,
+
+    #[msg("Invalid Payload Length")]
+    InvalidPayloadLength,
+
+    #[msg("Unsupported Payload Version")]
+    UnsupportedPayloadVersion,
+
+    #[msg("Unknown Payload Type")]
+    UnknownPayloadType,
+
+    #[msg("Payload Message Type Does Not Match The Instruction")]
+    MessageTypeMismatch,
+
+    #[msg("Config Is Not In The Legacy Layout")]
+    ConfigAlreadyMigrated,
+
+    #[msg("Emitter Is Disabled")]
+    EmitterDisabled,
+
+    #[msg("Signer Is Not The Pending Owner")]
+    NotPendingOwner,
+
+    #[msg("Signer Is Not An Allowed Relayer")]
+    InvalidRelayer,
+
+    #[msg("Relayer Already Added")]
+    RelayerAlreadyAdded,
+
+    #[msg("Relayer Not Found")]
+    RelayerNotFound,
+
+    #[msg("Relayer List Is Full")]
+    TooManyRelayers,
+
+    #[msg("Program Is Paused")]
+    ProgramPaused,
+
+    #[msg("Emitter Is Paused")]
+    EmitterPaused,
+
+    #[msg("Emitter Account Is Not In The Legacy Layout")]
+    EmitterAlreadyMigrated,
+
+    #[msg("VAA Consistency Level Is Too Low")]
+    ConsistencyLevelTooLow,
+
+    #[msg("VAA Is Too Old")]
+    VAAExpired,
+
+    #[msg("Token Bridge Transfer Is Not Addressed To The Sender PDA")]
+    RedeemerMismatch,
+
+    #[msg("Bridged Transfers Can Only Carry DepositToken Or TokenStream Messages")]
+    UnsupportedBridgedMessage,
+
+    #[msg("Bridged Amount Is Lower Than The Message Amount")]
+    InsufficientBridgedAmount,
+
+    #[msg("Target Emitter Is Not Registered For The Target Chain")]
+    TargetEmitterMismatch,
+
+    #[msg("Relayer Fee Is Above The Signed Or Scheduled Maximum")]
+    RelayerFeeTooHigh,
+
+    #[msg("Top Up Threshold Is Above The PDA Funding")]
+    InvalidPdaFunding,
+
+    #[msg("PDA Balance Is Not Below The Top Up Threshold")]
+    PdaAboveThreshold,
+
+    #[msg("VAA Sequence Is Not Recorded In The Bitmap")]
+    SequenceNotRecorded,
+
+    #[msg("VAA Retention Period Has Not Elapsed")]
+    RetentionNotElapsed,
+
+    #[msg("Invalid VAA Retention")]
+    InvalidVaaRetention,
+
+    #[msg("Processed VAA Is Not In The Legacy Layout")]
+    ProcessedVAANotLegacy,
+
+    #[msg("Stream Data Account Already Exists")]
+    StreamAlreadyExists,
+
+    #[msg("Target Chain Does Not Match Payload")]
+    TargetChainMismatch,
+
+    #[msg("Amount Cannot Be Expressed In Mint Decimals")]
+    AmountPrecisionLoss,
+
+    #[msg("Amount Too Small To Bridge")]
+    AmountBelowBridgeMinimum,
+
+    #[msg("Invalid Receiver Kind")]
+    InvalidReceiverKind,
 }
\ No newline at end of file
@@ -1,10 +1,61 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{AddressFormat, EmitterStatus, VaaStatus};
+
 #[event]
 pub struct InitializedPDA {
     pub pda: Pubkey,
 }
 
+#[event]
+pub struct PdaFunded {
+    pub pda: Pubkey,
+    pub amount: u64,
+    // Lamports sent to the PDA so far, this included
+    pub sponsored: u64,
+}
+
+#[event]
+pub struct BatchStreamProcessed {
+    pub chain_id: u16,
+    pub sequence: u64,
+    // Position of the stream in the batch
+    pub index: u8,
+    pub stream_id: u64,
+    pub data_account: Pubkey,
+    pub status: VaaStatus,
+    // Why the stream was skipped, 0 when started
+    pub error_code: u32,
+}
+
+#[event]
+pub struct VAARejected {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+    pub sequence: u64,
+    pub message_type: u8,
+    pub error_code: u32,
+}
+
+#[event]
+pub struct VaaRetentionSet {
+    pub vaa_retention: i64,
+}
+
+#[event]
+pub struct ProcessedVAAClosed {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+    pub sequence: u64,
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct PdaFundingSet {
+    pub pda_funding: u64,
+    pub pda_top_up_threshold: u64,
+}
+
 #[event]
 pub struct InitializedPDATokenAccount {
     pub pda: Pubkey,
@@ -15,83 +66,239 @@ pub struct InitializedPDATokenAccount {
 pub struct Initialized {
     pub owner: Pubkey,
     pub nonce: u32,
+    pub local_chain_id: u16,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub owner: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct OwnershipProposed {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferred {
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct AdminChanged {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct PauserChanged {
+    pub pauser: Pubkey,
+}
+
+#[event]
+pub struct RelayerAdded {
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct RelayerRemoved {
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct EmitterMigrated {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+    pub address_format: AddressFormat,
 }
 
 #[event]
 pub struct RegisteredChain {
     pub chain_id: u16,
-    pub emitter_addr: String,
+    pub emitter_addr: [u8; 32],
+    pub address_format: AddressFormat,
+    pub min_consistency_level: u8,
+    pub max_vaa_age: u32,
 }
 
 #[event]
-pub struct StoredMsg {
-    pub msg_type: u64,
-    pub sender: [u8; 32],
-    pub count: u64,
+pub struct EmitterUpdated {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+    pub status: EmitterStatus,
 }
 
 #[event]
-pub struct Deposited {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+pub struct EmitterFinalitySet {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+    pub min_consistency_level: u8,
+    pub max_vaa_age: u32,
 }
 
 #[event]
-pub struct StreamUpdated {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+pub struct FeeScheduleSet {
+    pub chain_id: u16,
+    pub max_fee: u64,
 }
 
 #[event]
-pub struct PausedResumed {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+pub struct EmitterDisabled {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
 }
 
 #[event]
-pub struct DirectTransferredNative {
-    pub sender: [u8; 32],
-    pub sender_chain: u16,
-    pub target_chain: u16,
-    pub receiver: [u8; 32],
+pub struct EmitterRemoved {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
 }
 
 #[event]
-pub struct DirectTransferredWrapped {
-    pub sender: [u8; 32],
-    pub sender_chain: u16,
-    pub target_chain: u16,
-    pub receiver: [u8; 32],
+pub struct Paused {
+    pub pauser: Pubkey,
+}
+
+#[event]
+pub struct Unpaused {
+    pub pauser: Pubkey,
+}
+
+#[event]
+pub struct EmitterPaused {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
 }
 
+#[event]
+pub struct EmitterUnpaused {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+}
+
+// Stream lifecycle, emitted once the zebec instruction of a VAA succeeded. `token_mint` is None
+// for native SOL, `sender` and `receiver` are the PDAs of the two parties.
+
 #[event]
 pub struct StreamCreated {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub data_account: Pubkey,
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub start_time: u64,
+    pub end_time: u64,
 }
 
 #[event]
-pub struct CancelCreated {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+pub struct StreamUpdated {
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub data_account: Pubkey,
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[event]
+pub struct Deposited {
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub sender: Pubkey,
 }
 
 #[event]
 pub struct SenderWithdrawCreated {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub sender: Pubkey,
 }
 
 #[event]
 pub struct InstantTransferCreated {
-    pub sender: [u8; 32],
-    pub current_count: u64,
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct PausedResumed {
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub data_account: Pubkey,
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct CancelCreated {
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub data_account: Pubkey,
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
 }
 
 #[event]
 pub struct ReceiverWithdrawCreated {
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub token_mint: Option<Pubkey>,
+    pub data_account: Pubkey,
+    pub sender: Pubkey,
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct DirectTransferredNative {
+    pub sender: [u8; 32],
+    pub sender_chain: u16,
+    pub target_chain: u16,
+    pub receiver: [u8; 32],
+    // Relayer fee taken out of the transferred amount
+    pub fee: u64,
+    // Requested amount, the part sent through the Token Bridge and the dust left in the sender PDA
+    pub amount: u64,
+    pub bridged_amount: u64,
+    pub dust: u64,
+}
+
+#[event]
+pub struct DirectTransferredWrapped {
     pub sender: [u8; 32],
-    pub current_count: u64,
+    pub sender_chain: u16,
+    pub target_chain: u16,
+    pub receiver: [u8; 32],
+    // Relayer fee taken out of the transferred amount
+    pub fee: u64,
+    // Requested amount, the part sent through the Token Bridge and the dust left in the sender PDA
+    pub amount: u64,
+    pub bridged_amount: u64,
+    pub dust: u64,
 }
 
 #[event]
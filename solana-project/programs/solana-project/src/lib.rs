@@ -1,16 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer as transfer_sol, Transfer as TransferSol};
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer as transfer_sol, Allocate, Assign, CreateAccount,
+    Transfer as TransferSol,
+};
+use anchor_lang::Discriminator;
 
 use anchor_lang::solana_program::instruction::Instruction;
 
-use anchor_spl::token::{approve, Approve};
+use anchor_spl::token::{approve, transfer as transfer_token, Approve, Transfer as TransferToken};
 
 use primitive_types::U256;
-use sha3::Digest;
-
-use byteorder::{BigEndian, WriteBytesExt};
-use hex::decode;
-use std::io::{Cursor, Write};
 use std::str::FromStr;
 mod constants;
 mod context;
@@ -19,6 +18,7 @@ mod events;
 mod payload;
 mod portal;
 mod state;
+mod vaa;
 mod wormhole;
 
 use constants::*;
@@ -27,11 +27,20 @@ use errors::*;
 use events::*;
 use payload::*;
 use portal::*;
+use state::*;
+use vaa::*;
 use wormhole::*;
 
 use anchor_lang::solana_program::program::invoke_signed;
 
+#[cfg(feature = "mainnet")]
+declare_id!("2w1QMXDTsWCmpdPHfjrL9SbkKeh3K37WMqJQUfnXHzRh");
+// Testnet builds run on devnet next to the Wormhole testnet deployment, see constants.rs
+#[cfg(any(feature = "devnet", feature = "testnet"))]
 declare_id!("3qAAmNxTHxeL6pKDC6nb2PmoCE6hgZM2QXtS88gBm3yL");
+// Address of solana_project-keypair.json, used for local validator deployments
+#[cfg(feature = "localnet")]
+declare_id!("AxJUYo5P9SL9f1XHxdqUSaAvGPqSbFNMcgQ9tZENyofB");
 
 #[program]
 pub mod solana_project {
@@ -41,160 +50,550 @@ pub mod solana_project {
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         ctx.accounts.config.owner = ctx.accounts.owner.key();
         ctx.accounts.config.nonce = 1;
+        ctx.accounts.config.local_chain_id = LOCAL_CHAIN_ID;
+        ctx.accounts.config.admin = ctx.accounts.owner.key();
+        ctx.accounts.config.pauser = ctx.accounts.owner.key();
+        ctx.accounts.config.relayers = vec![ctx.accounts.owner.key()];
+        ctx.accounts.config.pda_funding = DEFAULT_PDA_FUNDING;
+        ctx.accounts.config.pda_top_up_threshold = DEFAULT_PDA_TOP_UP_THRESHOLD;
+        ctx.accounts.config.vaa_retention = DEFAULT_VAA_RETENTION;
+        ctx.accounts.config.version = CONFIG_VERSION;
 
         emit!(Initialized {
             owner: ctx.accounts.config.owner,
-            nonce: ctx.accounts.config.nonce
+            nonce: ctx.accounts.config.nonce,
+            local_chain_id: LOCAL_CHAIN_ID
         });
         Ok(())
     }
 
-    pub fn register_chain(
-        ctx: Context<RegisterChain>,
-        chain_id: u16,
-        emitter_addr: String,
-    ) -> Result<()> {
+    // Moves a config account created by the first deployment to the current layout, with the
+    // defaults of initialize for every field it did not have
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        let legacy = {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data.len() == LegacyConfig::LEN && data[..8] == Config::discriminator(),
+                MessengerError::ConfigAlreadyMigrated
+            );
+            LegacyConfig::try_from_slice(&data[8..])?
+        };
         require!(
-            emitter_addr.len() == EVM_CHAIN_ADDRESS_LENGTH,
-            MessengerError::InvalidEmitterAddress
+            legacy.owner == ctx.accounts.owner.key(),
+            MessengerError::InvalidCaller
         );
 
-        ctx.accounts.emitter_acc.chain_id = chain_id;
-        ctx.accounts.emitter_acc.emitter_addr = emitter_addr.clone();
+        let rent_lamport = Rent::get()?.minimum_balance(Config::LEN);
+        let lamports = config_info.lamports();
+        if rent_lamport > lamports {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                TransferSol {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: config_info.clone(),
+                },
+            );
+            transfer_sol(cpi_ctx, rent_lamport - lamports)?;
+        }
+        config_info.realloc(Config::LEN, true)?;
+
+        let config = Config {
+            owner: legacy.owner,
+            nonce: legacy.nonce,
+            local_chain_id: LOCAL_CHAIN_ID,
+            admin: legacy.owner,
+            pauser: legacy.owner,
+            relayers: vec![legacy.owner],
+            pda_funding: DEFAULT_PDA_FUNDING,
+            pda_top_up_threshold: DEFAULT_PDA_TOP_UP_THRESHOLD,
+            vaa_retention: DEFAULT_VAA_RETENTION,
+            version: CONFIG_VERSION,
+            ..Default::default()
+        };
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
 
-        emit!(RegisteredChain {
-            chain_id: chain_id,
-            emitter_addr: emitter_addr
+        emit!(ConfigMigrated {
+            owner: legacy.owner,
+            version: CONFIG_VERSION
         });
         Ok(())
     }
 
-    pub fn initialize_pda(
-        ctx: Context<InitializePDA>,
-        _sender: [u8; 32],
-        _chain_id: u16,
-    ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAostedMA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
+    pub fn propose_owner(ctx: Context<UpdateConfig>, pending_owner: Pubkey) -> Result<()> {
+        ctx.accounts.config.pending_owner = pending_owner;
 
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
+        emit!(OwnershipProposed {
+            owner: ctx.accounts.config.owner,
+            pending_owner: pending_owner
+        });
+        Ok(())
+    }
 
-        let (vaa_key, _) = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        );
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        let previous_owner = ctx.accounts.config.owner;
+        ctx.accounts.config.owner = ctx.accounts.pending_owner.key();
+        ctx.accounts.config.pending_owner = Pubkey::default();
+
+        emit!(OwnershipTransferred {
+            previous_owner: previous_owner,
+            owner: ctx.accounts.config.owner
+        });
+        Ok(())
+    }
+
+    pub fn set_admin(ctx: Context<UpdateConfig>, admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.admin = admin;
+
+        emit!(AdminChanged { admin: admin });
+        Ok(())
+    }
+
+    pub fn set_pauser(ctx: Context<UpdateConfig>, pauser: Pubkey) -> Result<()> {
+        ctx.accounts.config.pauser = pauser;
+
+        emit!(PauserChanged { pauser: pauser });
+        Ok(())
+    }
 
+    pub fn set_pda_funding(
+        ctx: Context<UpdateConfig>,
+        pda_funding: u64,
+        pda_top_up_threshold: u64,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
+            pda_top_up_threshold <= pda_funding,
+            MessengerError::InvalidPdaFunding
         );
+        ctx.accounts.config.pda_funding = pda_funding;
+        ctx.accounts.config.pda_top_up_threshold = pda_top_up_threshold;
+
+        emit!(PdaFundingSet {
+            pda_funding: pda_funding,
+            pda_top_up_threshold: pda_top_up_threshold
+        });
+        Ok(())
+    }
 
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
+    pub fn set_vaa_retention(ctx: Context<UpdateConfig>, vaa_retention: i64) -> Result<()> {
         require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
+            (0..=MAX_VAA_RETENTION).contains(&vaa_retention),
+            MessengerError::InvalidVaaRetention
         );
+        ctx.accounts.config.vaa_retention = vaa_retention;
+
+        emit!(VaaRetentionSet {
+            vaa_retention: vaa_retention
+        });
+        Ok(())
+    }
 
-        // Encoded String
-        let encoded_str = vaa.payload.clone();
+    // Returns the rent of a ProcessedVAA to its relayer, the sequence bitmap keeps the VAA from
+    // being replayed
+    pub fn close_processed_vaa(
+        ctx: Context<CloseProcessedVAA>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.sequence_bitmap.is_set(sequence),
+            MessengerError::SequenceNotRecorded
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let closable_at = ctx
+            .accounts
+            .processed_vaa
+            .processed_at
+            .checked_add(ctx.accounts.config.vaa_retention)
+            .ok_or(MessengerError::Overflow)?;
+        require!(now >= closable_at, MessengerError::RetentionNotElapsed);
+
+        emit!(ProcessedVAAClosed {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr,
+            sequence: sequence,
+            relayer: ctx.accounts.relayer.key()
+        });
+        Ok(())
+    }
 
-        // Decode Encoded String and Store Value based upon the code sent on message passing
-        let code = get_u8(encoded_str[0..1].to_vec());
+    // Closes a ProcessedVAA created before the sequence bitmaps. Its sequence is set in the
+    // bitmap first so the VAA stays consumed, and the rent goes to an account of the admin's
+    // choice as the legacy layout records no relayer.
+    pub fn close_legacy_processed_vaa(
+        ctx: Context<CloseLegacyProcessedVAA>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        let processed_info = ctx.accounts.processed_vaa.to_account_info();
+        {
+            let data = processed_info.try_borrow_data()?;
+            require!(
+                data.len() == LegacyProcessedVAA::LEN
+                    && data[..8] == ProcessedVAA::discriminator(),
+                MessengerError::ProcessedVAANotLegacy
+            );
+        }
+
+        let sequence_bitmap = &mut ctx.accounts.sequence_bitmap;
+        // Freshly created bitmap
+        if sequence_bitmap.emitter_addr == [0; 32] {
+            sequence_bitmap.chain_id = chain_id;
+            sequence_bitmap.emitter_addr = emitter_addr;
+            sequence_bitmap.bucket = sequence / SEQUENCES_PER_BITMAP;
+        }
+        sequence_bitmap.set(sequence);
+
+        let rent_receiver_info = ctx.accounts.rent_receiver.to_account_info();
+        let lamports = processed_info.lamports();
+        **rent_receiver_info.try_borrow_mut_lamports()? = rent_receiver_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(MessengerError::Overflow)?;
+        **processed_info.try_borrow_mut_lamports()? = 0;
+        processed_info.try_borrow_mut_data()?.fill(0);
+
+        emit!(ProcessedVAAClosed {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr,
+            sequence: sequence,
+            relayer: ctx.accounts.rent_receiver.key()
+        });
+        Ok(())
+    }
 
-        require!(code == 18, MessengerError::InvalidPayload);
-        let account_pda = Pubkey::find_program_address(
-            &[&encoded_str[1..33], &vaa.emitter_chain.to_be_bytes()],
-            ctx.program_id,
-        )
-        .0;
+    pub fn add_relayer(ctx: Context<UpdateConfig>, relayer: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
         require!(
-            account_pda == ctx.accounts.pda_account.key(),
-            MessengerError::InvalidPDAAccount
+            !config.is_relayer(&relayer),
+            MessengerError::RelayerAlreadyAdded
+        );
+        require!(
+            config.relayers.len() < MAX_RELAYERS,
+            MessengerError::TooManyRelayers
         );
+        config.relayers.push(relayer);
 
-        let to_chain_id = get_u256(encoded_str[33..65].to_vec());
+        emit!(RelayerAdded { relayer: relayer });
+        Ok(())
+    }
+
+    pub fn remove_relayer(ctx: Context<UpdateConfig>, relayer: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let index = config
+            .relayers
+            .iter()
+            .position(|key| *key == relayer)
+            .ok_or(MessengerError::RelayerNotFound)?;
+        config.relayers.remove(index);
+
+        emit!(RelayerRemoved { relayer: relayer });
+        Ok(())
+    }
+
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+
+        emit!(Paused {
+            pauser: ctx.accounts.pauser.key()
+        });
+        Ok(())
+    }
+
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+
+        emit!(Unpaused {
+            pauser: ctx.accounts.pauser.key()
+        });
+        Ok(())
+    }
+
+    pub fn pause_emitter(
+        ctx: Context<SetEmitterPaused>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.emitter_acc.paused = true;
+
+        emit!(EmitterPaused {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr
+        });
+        Ok(())
+    }
+
+    pub fn unpause_emitter(
+        ctx: Context<SetEmitterPaused>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.emitter_acc.paused = false;
+
+        emit!(EmitterUnpaused {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr
+        });
+        Ok(())
+    }
 
+    pub fn register_chain(
+        ctx: Context<RegisterChain>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+        address_format: AddressFormat,
+        min_consistency_level: u8,
+        max_vaa_age: u32,
+    ) -> Result<()> {
         require!(
-            to_chain_id == U256::from_str("1").unwrap(),
-            MessengerError::InvalidToChainId
+            address_format.is_valid(&emitter_addr),
+            MessengerError::InvalidEmitterAddress
         );
 
-        let rent_lamport = Rent::default().minimum_balance(1);
+        ctx.accounts.emitter_acc.chain_id = chain_id;
+        ctx.accounts.emitter_acc.emitter_addr = emitter_addr;
+        ctx.accounts.emitter_acc.address_format = address_format;
+        ctx.accounts.emitter_acc.status = EmitterStatus::Active;
+        ctx.accounts.emitter_acc.min_consistency_level = min_consistency_level;
+        ctx.accounts.emitter_acc.max_vaa_age = max_vaa_age;
+
+        emit!(RegisteredChain {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr,
+            address_format: address_format,
+            min_consistency_level: min_consistency_level,
+            max_vaa_age: max_vaa_age
+        });
+        Ok(())
+    }
 
-        let cpi_transfer_sol = TransferSol {
-            from: ctx.accounts.zebec_eoa.to_account_info(),
-            to: ctx.accounts.pda_account.to_account_info(),
+    // Moves an emitter registered by the first deployment to the account seeded by its address,
+    // with the policies of register_chain, and closes the old account
+    pub fn migrate_emitter(
+        ctx: Context<MigrateEmitter>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+        address_format: AddressFormat,
+        min_consistency_level: u8,
+        max_vaa_age: u32,
+    ) -> Result<()> {
+        let legacy_info = ctx.accounts.legacy_emitter_acc.to_account_info();
+        let legacy = {
+            let data = legacy_info.try_borrow_data()?;
+            require!(
+                data.len() > 8 && data[..8] == EmitterAddrAccount::discriminator(),
+                MessengerError::EmitterAlreadyMigrated
+            );
+            LegacyEmitterAddrAccount::deserialize(&mut &data[8..])?
         };
-        let cpi_transfer_sol_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            cpi_transfer_sol,
+        //check emitter address
+        require!(
+            legacy.chain_id == chain_id
+                && hex::decode(legacy.emitter_addr.as_str())
+                    .map_or(false, |addr| addr[..] == emitter_addr[..]),
+            MessengerError::InvalidEmitterAddress
+        );
+        require!(
+            address_format.is_valid(&emitter_addr),
+            MessengerError::InvalidEmitterAddress
         );
-        transfer_sol(cpi_transfer_sol_ctx, rent_lamport + 5000000)?;
 
-        emit!(InitializedPDA { pda: account_pda });
+        ctx.accounts.emitter_acc.chain_id = chain_id;
+        ctx.accounts.emitter_acc.emitter_addr = emitter_addr;
+        ctx.accounts.emitter_acc.address_format = address_format;
+        ctx.accounts.emitter_acc.status = EmitterStatus::Active;
+        ctx.accounts.emitter_acc.min_consistency_level = min_consistency_level;
+        ctx.accounts.emitter_acc.max_vaa_age = max_vaa_age;
+
+        let admin_info = ctx.accounts.admin.to_account_info();
+        let lamports = legacy_info.lamports();
+        **admin_info.try_borrow_mut_lamports()? = admin_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(MessengerError::Overflow)?;
+        **legacy_info.try_borrow_mut_lamports()? = 0;
+        legacy_info.try_borrow_mut_data()?.fill(0);
+
+        emit!(EmitterMigrated {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr,
+            address_format: address_format
+        });
+        Ok(())
+    }
+
+    pub fn update_emitter(
+        ctx: Context<ManageEmitter>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+        status: EmitterStatus,
+    ) -> Result<()> {
+        ctx.accounts.emitter_acc.status = status;
+
+        emit!(EmitterUpdated {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr,
+            status: status
+        });
+        Ok(())
+    }
 
+    pub fn set_emitter_finality(
+        ctx: Context<ManageEmitter>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+        min_consistency_level: u8,
+        max_vaa_age: u32,
+    ) -> Result<()> {
+        ctx.accounts.emitter_acc.min_consistency_level = min_consistency_level;
+        ctx.accounts.emitter_acc.max_vaa_age = max_vaa_age;
+
+        emit!(EmitterFinalitySet {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr,
+            min_consistency_level: min_consistency_level,
+            max_vaa_age: max_vaa_age
+        });
         Ok(())
     }
 
-    pub fn initialize_pda_token_account(
-        ctx: Context<InitializePDATokenAccount>,
+    pub fn set_fee_schedule(
+        ctx: Context<SetFeeSchedule>,
+        chain_id: u16,
+        max_fee: u64,
+    ) -> Result<()> {
+        ctx.accounts.fee_schedule.chain_id = chain_id;
+        ctx.accounts.fee_schedule.max_fee = max_fee;
+
+        emit!(FeeScheduleSet {
+            chain_id: chain_id,
+            max_fee: max_fee
+        });
+        Ok(())
+    }
+
+    pub fn disable_emitter(
+        ctx: Context<DisableEmitter>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.emitter_acc.status = EmitterStatus::Disabled;
+
+        emit!(EmitterDisabled {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr
+        });
+        Ok(())
+    }
+
+    pub fn remove_emitter(
+        _ctx: Context<RemoveEmitter>,
+        chain_id: u16,
+        emitter_addr: [u8; 32],
+    ) -> Result<()> {
+        emit!(EmitterRemoved {
+            chain_id: chain_id,
+            emitter_addr: emitter_addr
+        });
+        Ok(())
+    }
+
+    pub fn initialize_pda(
+        ctx: Context<InitializePDA>,
         _sender: [u8; 32],
-        _chain_id: u16,
+        chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAostedMA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.zebec_eoa.key(),
+        )?;
 
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
+        let payload = InitializePdaPayload::deserialize(&mut vaa.payload.as_slice())?;
 
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
+        let account_pda = Pubkey::find_program_address(
+            &[&payload.account, &vaa.emitter_chain.to_be_bytes()],
+            ctx.program_id,
         )
         .0;
-
         require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
+            account_pda == ctx.accounts.pda_account.key(),
+            MessengerError::InvalidPDAAccount
         );
 
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        let amount = Rent::get()?.minimum_balance(0) + ctx.accounts.config.pda_funding;
+        fund_pda(
+            &ctx.accounts.zebec_eoa,
+            &ctx.accounts.pda_account,
+            &mut ctx.accounts.pda_sponsorship,
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        emit!(InitializedPDA { pda: account_pda });
+
+        Ok(())
+    }
+
+    // Refills a sender PDA that fell below the top up threshold back to its funding amount
+    pub fn top_up_pda(ctx: Context<TopUpPDA>, _sender: [u8; 32], _chain_id: u16) -> Result<()> {
+        require!(!ctx.accounts.config.paused, MessengerError::ProgramPaused);
+
+        let rent_lamport = Rent::get()?.minimum_balance(0);
+        let balance = ctx.accounts.pda_account.lamports();
         require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
+            balance < rent_lamport + ctx.accounts.config.pda_top_up_threshold,
+            MessengerError::PdaAboveThreshold
         );
 
-        // Encoded String
-        let encoded_str = vaa.payload.clone();
+        let amount = rent_lamport + ctx.accounts.config.pda_funding - balance;
+        fund_pda(
+            &ctx.accounts.zebec_eoa,
+            &ctx.accounts.pda_account,
+            &mut ctx.accounts.pda_sponsorship,
+            &ctx.accounts.system_program,
+            amount,
+        )
+    }
+
+    pub fn initialize_pda_token_account(
+        ctx: Context<InitializePDATokenAccount>,
+        _sender: [u8; 32],
+        chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.zebec_eoa.key(),
+        )?;
 
-        // Decode Encoded String and Store   Value based upon the code sent on message passing
-        let code = get_u8(encoded_str[0..1].to_vec());
+        let payload = InitializeTokenAccountPayload::deserialize(&mut vaa.payload.as_slice())?;
 
-        require!(code == 19, MessengerError::InvalidPayload);
         let account_pda = Pubkey::find_program_address(
-            &[&encoded_str[1..33], &vaa.emitter_chain.to_be_bytes()],
+            &[&payload.account, &vaa.emitter_chain.to_be_bytes()],
             ctx.program_id,
         )
         .0;
-        let token_mint_array: [u8; 32] = encoded_str[33..65].try_into().unwrap();
-        let token_mint = Pubkey::new_from_array(token_mint_array);
-        let to_chain_id = get_u256(encoded_str[65..97].to_vec());
-
-        require!(
-            to_chain_id == U256::from_str("1").unwrap(),
-            MessengerError::InvalidToChainId
-        );
+        let token_mint = Pubkey::new_from_array(payload.token_mint);
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         require!(
             account_pda == ctx.accounts.pda_account.key(),
@@ -220,35 +619,40 @@ pub mod solana_project {
         target_chain: u16,
         fee: u64,
     ) -> Result<()> {
-        //Hash a VAA Extracts and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.zebec_eoa.key(),
+        )?;
 
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
+        let payload = XstreamDirectTransferPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
+        //check target chain and mint
         require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
+            target_chain == payload.target_chain,
+            MessengerError::TargetChainMismatch
         );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
         require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
+            ctx.accounts.mint.key() == Pubkey::new(&payload.token_mint),
+            MessengerError::MintKeyMismatch
         );
 
-        let payload = decode_xstream_direct(vaa.payload);
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+        let max_relayer_fee = denormalize_amount(
+            payload.max_relayer_fee,
+            payload.decimals,
+            ctx.accounts.mint.decimals,
+        )?;
 
         //check sender
         let sender_stored = payload.sender;
@@ -267,17 +671,31 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
+        //check relayer fee
+        let max_fee = fee_schedule_cap(&ctx.accounts.fee_schedule, ctx.accounts.mint.decimals)?;
+        require!(
+            fee <= max_relayer_fee && fee <= max_fee && fee <= amount,
+            MessengerError::RelayerFeeTooHigh
+        );
+
+        //bridge what the Token Bridge can carry, the dust stays in the sender PDA
+        let bridged_amount = bridgeable_amount(amount, ctx.accounts.mint.decimals)?;
+
         emit!(DirectTransferredNative {
             sender: sender,
             sender_chain: chain_id,
             target_chain: target_chain,
             receiver: receiver_stored,
+            fee: fee,
+            amount,
+            bridged_amount,
+            dust: amount - bridged_amount,
         });
 
         transfer_native(
             ctx,
             sender,
-            payload.amount,
+            bridged_amount,
             chain_id,
             target_chain,
             fee,
@@ -295,35 +713,44 @@ pub mod solana_project {
         target_chain: u16,
         fee: u64,
     ) -> Result<()> {
-        //Hash a VAA Extracts and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            sender_chain,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.zebec_eoa.key(),
+        )?;
 
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
+        let payload = XstreamDirectTransferPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
+        //check target chain and mint
         require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
+            target_chain == payload.target_chain,
+            MessengerError::TargetChainMismatch
         );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
         require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
+            ctx.accounts.wrapped_mint.key() == Pubkey::new(&payload.token_mint),
+            MessengerError::MintKeyMismatch
         );
 
-        let payload = decode_xstream_direct(vaa.payload);
+        //scale amounts to the mint decimals
+        let amount = denormalize_amount(
+            payload.amount,
+            payload.decimals,
+            ctx.accounts.wrapped_mint.decimals,
+        )?;
+        let max_relayer_fee = denormalize_amount(
+            payload.max_relayer_fee,
+            payload.decimals,
+            ctx.accounts.wrapped_mint.decimals,
+        )?;
+
         //check sender
         let sender_stored = payload.sender;
         require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
@@ -340,17 +767,34 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
+        //check relayer fee
+        let max_fee = fee_schedule_cap(
+            &ctx.accounts.fee_schedule,
+            ctx.accounts.wrapped_mint.decimals,
+        )?;
+        require!(
+            fee <= max_relayer_fee && fee <= max_fee && fee <= amount,
+            MessengerError::RelayerFeeTooHigh
+        );
+
+        //bridge what the Token Bridge can carry, the dust stays in the sender PDA
+        let bridged_amount = bridgeable_amount(amount, ctx.accounts.wrapped_mint.decimals)?;
+
         emit!(DirectTransferredWrapped {
             sender: sender,
             sender_chain: sender_chain,
             target_chain: target_chain,
             receiver: receiver_stored,
+            fee: fee,
+            amount,
+            bridged_amount,
+            dust: amount - bridged_amount,
         });
 
         transfer_wrapped(
             ctx,
             sender,
-            payload.amount,
+            bridged_amount,
             sender_chain,
             target_chain,
             fee,
@@ -358,56 +802,240 @@ pub mod solana_project {
         )
     }
 
-    pub fn xstream_withdraw(
-        ctx: Context<XstreamWithdraw>,
+    //create and execute direct transfer native to the target chain Messenger, with a memo
+    pub fn xstream_direct_transfer_native_with_payload(
+        ctx: Context<XstreamDirectTransferNativeWithPayload>,
         sender: [u8; 32],
-        from_chain_id: u16,
+        chain_id: u16,
+        target_chain: u16,
+        fee: u64,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.zebec_eoa.key(),
+        )?;
 
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
+        let payload = XstreamDirectTransferPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
+        //check target chain and mint
         require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
+            target_chain == payload.target_chain,
+            MessengerError::TargetChainMismatch
         );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
         require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
+            ctx.accounts.mint.key() == Pubkey::new(&payload.token_mint),
+            MessengerError::MintKeyMismatch
         );
 
-        let payload = decode_xstream_withdraw(vaa.payload);
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+        let max_relayer_fee = denormalize_amount(
+            payload.max_relayer_fee,
+            payload.decimals,
+            ctx.accounts.mint.decimals,
+        )?;
 
-        //check Mint passed
-        let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
-        require!(
-            mint_pubkey_passed == Pubkey::new(&payload.token_mint),
-            MessengerError::MintKeyMismatch
-        );
+        //check sender
+        require!(sender == payload.sender, MessengerError::PdaSenderMismatch);
 
-        //check data account
-        let data_account_passed: Pubkey = ctx.accounts.data_account.key();
+        //check target Messenger
         require!(
-            data_account_passed == Pubkey::new(&payload.data_account),
-            MessengerError::DataAccountMismatch
+            ctx.accounts.target_emitter_acc.status == EmitterStatus::Active,
+            MessengerError::EmitterDisabled
         );
 
-        //check sender
+        let memo = TransferMemoPayload {
+            to_chain_id: encode_chain_id(target_chain),
+            sender,
+            sender_chain: chain_id,
+            receiver: payload.receiver,
+            request_sequence: vaa.sequence,
+            purpose: TransferPurpose::DirectTransfer as u8,
+        };
+
+        //check relayer fee
+        let max_fee = fee_schedule_cap(&ctx.accounts.fee_schedule, ctx.accounts.mint.decimals)?;
+        require!(
+            fee <= max_relayer_fee && fee <= max_fee && fee <= amount,
+            MessengerError::RelayerFeeTooHigh
+        );
+
+        //the relayer is paid here, bridge what the Token Bridge can carry of the rest, the dust
+        //stays in the sender PDA
+        let bridged_amount = bridgeable_amount(amount - fee, ctx.accounts.mint.decimals)?;
+
+        emit!(DirectTransferredNative {
+            sender: sender,
+            sender_chain: chain_id,
+            target_chain: target_chain,
+            receiver: payload.receiver,
+            fee: fee,
+            amount,
+            bridged_amount,
+            dust: amount - fee - bridged_amount,
+        });
+
+        transfer_native_with_payload(
+            ctx,
+            sender,
+            bridged_amount,
+            chain_id,
+            target_chain,
+            fee,
+            memo,
+        )
+    }
+
+    //create and execute direct transfer wrapped to the target chain Messenger, with a memo
+    pub fn xstream_direct_transfer_wrapped_with_payload(
+        ctx: Context<XstreamDirectTransferWrappedWithPayload>,
+        sender: [u8; 32],
+        sender_chain: u16,
+        _token_address: Vec<u8>,
+        _token_chain: u16,
+        target_chain: u16,
+        fee: u64,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            sender_chain,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.zebec_eoa.key(),
+        )?;
+
+        let payload = XstreamDirectTransferPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //check target chain and mint
+        require!(
+            target_chain == payload.target_chain,
+            MessengerError::TargetChainMismatch
+        );
+        require!(
+            ctx.accounts.wrapped_mint.key() == Pubkey::new(&payload.token_mint),
+            MessengerError::MintKeyMismatch
+        );
+
+        //scale amounts to the mint decimals
+        let amount = denormalize_amount(
+            payload.amount,
+            payload.decimals,
+            ctx.accounts.wrapped_mint.decimals,
+        )?;
+        let max_relayer_fee = denormalize_amount(
+            payload.max_relayer_fee,
+            payload.decimals,
+            ctx.accounts.wrapped_mint.decimals,
+        )?;
+
+        //check sender
+        require!(sender == payload.sender, MessengerError::PdaSenderMismatch);
+
+        //check target Messenger
+        require!(
+            ctx.accounts.target_emitter_acc.status == EmitterStatus::Active,
+            MessengerError::EmitterDisabled
+        );
+
+        let memo = TransferMemoPayload {
+            to_chain_id: encode_chain_id(target_chain),
+            sender,
+            sender_chain,
+            receiver: payload.receiver,
+            request_sequence: vaa.sequence,
+            purpose: TransferPurpose::DirectTransfer as u8,
+        };
+
+        //check relayer fee
+        let max_fee = fee_schedule_cap(
+            &ctx.accounts.fee_schedule,
+            ctx.accounts.wrapped_mint.decimals,
+        )?;
+        require!(
+            fee <= max_relayer_fee && fee <= max_fee && fee <= amount,
+            MessengerError::RelayerFeeTooHigh
+        );
+
+        //the relayer is paid here, bridge what the Token Bridge can carry of the rest, the dust
+        //stays in the sender PDA
+        let bridged_amount = bridgeable_amount(amount - fee, ctx.accounts.wrapped_mint.decimals)?;
+
+        emit!(DirectTransferredWrapped {
+            sender: sender,
+            sender_chain: sender_chain,
+            target_chain: target_chain,
+            receiver: payload.receiver,
+            fee: fee,
+            amount,
+            bridged_amount,
+            dust: amount - fee - bridged_amount,
+        });
+
+        transfer_wrapped_with_payload(
+            ctx,
+            sender,
+            bridged_amount,
+            sender_chain,
+            target_chain,
+            fee,
+            memo,
+        )
+    }
+
+    // Only for ReceiverKind::ChainPda streams, Solana wallets withdraw through zebec directly
+    pub fn xstream_withdraw(
+        ctx: Context<XstreamWithdraw>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamWithdrawPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //check Mint passed
+        let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
+        require!(
+            mint_pubkey_passed == Pubkey::new(&payload.token_mint),
+            MessengerError::MintKeyMismatch
+        );
+
+        //check data account
+        let data_account_passed: Pubkey = ctx.accounts.data_account.key();
+        require!(
+            data_account_passed
+                == stream_data_account(&payload.depositor, from_chain_id, payload.stream_id).0,
+            MessengerError::DataAccountMismatch
+        );
+
+        //check sender
         let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
         let sender_stored = payload.depositor;
 
@@ -461,6 +1089,32 @@ pub mod solana_project {
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
         zebec::cpi::withdraw_token_stream(cpi_ctx)?;
+
+        emit!(ReceiverWithdrawCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+
+        let report = WithdrawalCompletedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            withdrawer: payload.withdrawer,
+            depositor: payload.depositor,
+            token_mint: payload.token_mint,
+            data_account: ctx.accounts.data_account.key().to_bytes(),
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
         Ok(())
     }
 
@@ -471,36 +1125,21 @@ pub mod solana_project {
         from_chain_id: u16,
     ) -> Result<()> {
         msg!("xstream start");
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
-
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
-
-        require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
-        );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
-        require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
-        );
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let payload = decode_xstream(vaa.payload);
-        //let payload = XstreamStartPayload::try_from_slice(&vaa.payload[1..])?;
+        let payload = XstreamStartPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check Mint passed
         let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
@@ -509,6 +1148,10 @@ pub mod solana_project {
             MessengerError::MintKeyMismatch
         );
 
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+
         //check sender
         let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
         let sender_stored = payload.sender;
@@ -528,15 +1171,23 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
-        //check pdaReceivers
-        let chain_id_seed = chain_id_stored.to_be_bytes();
-        let receiver_derived_pubkey: (Pubkey, u8) =
-            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        //check receiver
         require!(
-            pda_receiver_passed == receiver_derived_pubkey.0,
+            pda_receiver_passed
+                == receiver_account(&receiver_stored, payload.receiver_kind, from_chain_id),
             MessengerError::ReceiverDerivedKeyMismatch
         );
 
+        create_stream_data_account(
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.data_account.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &sender,
+            from_chain_id,
+            payload.stream_id,
+            STREAM_TOKEN_SIZE,
+        )?;
+
         let zebec_program = ctx.accounts.zebec_program.to_account_info();
         let zebec_accounts = zebec::cpi::accounts::TokenStream {
             dest_account: ctx.accounts.dest_account.to_account_info(),
@@ -559,47 +1210,236 @@ pub mod solana_project {
             cpi_ctx,
             payload.start_time,
             payload.end_time,
-            payload.amount,
+            amount,
             payload.can_cancel == 1,
             payload.can_update == 1,
         )?;
+
+        emit!(StreamCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            amount,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        });
+
+        let report = StreamCreatedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            sender: payload.sender,
+            receiver: payload.receiver,
+            token_mint: payload.token_mint,
+            data_account: ctx.accounts.data_account.key().to_bytes(),
+            amount,
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
         Ok(())
     }
 
-    pub fn xstream_update(
-        ctx: Context<XstreamUpdate>,
+    // Starts every stream of a batch VAA. Streams that can never be started are skipped so the
+    // rest of the batch goes through, each stream gets a BatchStreamProcessed event and the
+    // outcome of all of them is reported back in one message. The checks made here cannot catch
+    // everything zebec rejects, such as a vault that cannot cover a stream, and a zebec CPI that
+    // fails aborts the whole batch. The VAA is left unconsumed, to be retried once fixed or
+    // rejected with report_execution_failed.
+    pub fn xstream_batch_start<'info>(
+        ctx: Context<'_, '_, '_, 'info, XstreamBatchStart<'info>>,
         sender: [u8; 32],
         from_chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
+        let payload = XstreamBatchStartPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
+        //check Mint passed
         require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
+            ctx.accounts.mint.key() == Pubkey::new(&payload.token_mint),
+            MessengerError::MintKeyMismatch
         );
 
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
+        //check sender
+        require!(sender == payload.sender, MessengerError::PdaSenderMismatch);
+
         require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
+            ctx.remaining_accounts.len() == payload.items.len() * 2,
+            MessengerError::CountMismatch
         );
 
-        let payload = decode_xstream_update(vaa.payload);
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut results = Vec::with_capacity(payload.items.len());
+        for (index, (item, accounts)) in payload
+            .items
+            .iter()
+            .zip(ctx.remaining_accounts.chunks(2))
+            .enumerate()
+        {
+            let index = u8::try_from(index).map_err(|_| MessengerError::InvalidCount)?;
+            let dest_account = &accounts[0];
+            let data_account = &accounts[1];
+
+            let receiver = receiver_account(&item.receiver, item.receiver_kind, from_chain_id);
+            let checked = if dest_account.key() != receiver {
+                Err(MessengerError::ReceiverDerivedKeyMismatch)
+            } else if data_account.key()
+                != stream_data_account(&sender, from_chain_id, item.stream_id).0
+            {
+                Err(MessengerError::DataAccountMismatch)
+            } else if data_account.owner == &zebec::ID {
+                Err(MessengerError::StreamAlreadyExists)
+            } else {
+                denormalize_amount(item.amount, payload.decimals, ctx.accounts.mint.decimals)
+            };
+            let amount = match checked {
+                Ok(amount) => amount,
+                Err(error) => {
+                    emit!(BatchStreamProcessed {
+                        chain_id: vaa.emitter_chain,
+                        sequence: vaa.sequence,
+                        index: index,
+                        stream_id: item.stream_id,
+                        data_account: data_account.key(),
+                        status: VaaStatus::Rejected,
+                        error_code: error.into()
+                    });
+                    results.push(BatchStreamResult {
+                        stream_id: item.stream_id,
+                        data_account: data_account.key().to_bytes(),
+                        amount: 0,
+                        error_code: error.into(),
+                    });
+                    continue;
+                }
+            };
+
+            create_stream_data_account(
+                ctx.accounts.payer.to_account_info(),
+                data_account.clone(),
+                ctx.accounts.system_program.to_account_info(),
+                &sender,
+                from_chain_id,
+                item.stream_id,
+                STREAM_TOKEN_SIZE,
+            )?;
+
+            let zebec_accounts = zebec::cpi::accounts::TokenStream {
+                dest_account: dest_account.clone(),
+                source_account: ctx.accounts.source_account.to_account_info(),
+                fee_owner: ctx.accounts.fee_owner.to_account_info(),
+                fee_vault_data: ctx.accounts.fee_vault_data.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                data_account: data_account.clone(),
+                withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.zebec_program.to_account_info(),
+                zebec_accounts,
+                signer_seeds,
+            );
+            zebec::cpi::token_stream(
+                cpi_ctx,
+                item.start_time,
+                item.end_time,
+                amount,
+                item.can_cancel == 1,
+                item.can_update == 1,
+            )?;
+
+            emit!(StreamCreated {
+                vaa_hash: vaa.hash,
+                emitter_chain: vaa.emitter_chain,
+                sequence: vaa.sequence,
+                token_mint: Some(ctx.accounts.mint.key()),
+                amount,
+                data_account: data_account.key(),
+                sender: ctx.accounts.source_account.key(),
+                receiver: dest_account.key(),
+                start_time: item.start_time,
+                end_time: item.end_time,
+            });
+            emit!(BatchStreamProcessed {
+                chain_id: vaa.emitter_chain,
+                sequence: vaa.sequence,
+                index: index,
+                stream_id: item.stream_id,
+                data_account: data_account.key(),
+                status: VaaStatus::Executed,
+                error_code: 0
+            });
+            results.push(BatchStreamResult {
+                stream_id: item.stream_id,
+                data_account: data_account.key().to_bytes(),
+                amount,
+                error_code: 0,
+            });
+        }
+
+        let report = BatchStreamsProcessedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            results,
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
+        Ok(())
+    }
+
+    pub fn xstream_update(
+        ctx: Context<XstreamUpdate>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamUpdatePayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check Mint passed
         let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
@@ -608,10 +1448,15 @@ pub mod solana_project {
             MessengerError::MintKeyMismatch
         );
 
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+
         //check data account
         let data_account_passed: Pubkey = ctx.accounts.data_account.key();
         require!(
-            data_account_passed == Pubkey::new(&payload.data_account),
+            data_account_passed
+                == stream_data_account(&payload.sender, from_chain_id, payload.stream_id).0,
             MessengerError::DataAccountMismatch
         );
 
@@ -634,11 +1479,9 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
-        //check pdaReceiver
-        let receiver_derived_pubkey: (Pubkey, u8) =
-            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        //check receiver, the data account keeps the receiver kind picked at start
         require!(
-            pda_receiver_passed == receiver_derived_pubkey.0,
+            is_receiver_account(&pda_receiver_passed, &receiver_stored, from_chain_id),
             MessengerError::ReceiverDerivedKeyMismatch
         );
 
@@ -654,12 +1497,20 @@ pub mod solana_project {
         let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
-        zebec::cpi::token_stream_update(
-            cpi_ctx,
-            payload.start_time,
-            payload.end_time,
-            payload.amount,
-        )?;
+        zebec::cpi::token_stream_update(cpi_ctx, payload.start_time, payload.end_time, amount)?;
+
+        emit!(StreamUpdated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            amount,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        });
         Ok(())
     }
 
@@ -668,43 +1519,33 @@ pub mod solana_project {
         sender: [u8; 32],
         from_chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
-
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
-
-        require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
-        );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
-        require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
-        );
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let payload = decode_xstream_deposit(vaa.payload);
+        let payload = XstreamDepositPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check Mint passed
-        let mint_pubkey_passed: Pubkey = Pubkey::new(&payload.token_mint);
+        let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
         require!(
             mint_pubkey_passed == Pubkey::new(&payload.token_mint),
             MessengerError::MintKeyMismatch
         );
 
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+
         //check sender
         let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
         let sender_stored = payload.sender;
@@ -739,7 +1580,16 @@ pub mod solana_project {
         let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
-        zebec::cpi::deposit_token(cpi_ctx, payload.amount)?;
+        zebec::cpi::deposit_token(cpi_ctx, amount)?;
+
+        emit!(Deposited {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            amount,
+            sender: ctx.accounts.source_account.key(),
+        });
         Ok(())
     }
 
@@ -748,35 +1598,21 @@ pub mod solana_project {
         sender: [u8; 32],
         from_chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
-
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
-
-        require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
-        );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
-        require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
-        );
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let payload = decode_deposit_withdraw(vaa.payload);
+        let payload = XstreamWithdrawDepositPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check Mint passed
         let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
@@ -785,6 +1621,10 @@ pub mod solana_project {
             MessengerError::MintKeyMismatch
         );
 
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+
         //check sender
         let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
         let sender_stored = payload.withdrawer;
@@ -820,7 +1660,16 @@ pub mod solana_project {
         let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
-        zebec::cpi::token_withdrawal(cpi_ctx, payload.amount)?;
+        zebec::cpi::token_withdrawal(cpi_ctx, amount)?;
+
+        emit!(SenderWithdrawCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            amount,
+            sender: ctx.accounts.source_account.key(),
+        });
         Ok(())
     }
 
@@ -829,40 +1678,27 @@ pub mod solana_project {
         sender: [u8; 32],
         from_chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
-
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
-
-        require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
-        );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
-        require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
-        );
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let payload = decode_xstream_pause(vaa.payload);
+        let payload = XstreamPausePayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check data account
         let data_account_passed: Pubkey = ctx.accounts.data_account.key();
         require!(
-            data_account_passed == Pubkey::new(&payload.data_account),
+            data_account_passed
+                == stream_data_account(&payload.depositor, from_chain_id, payload.stream_id).0,
             MessengerError::DataAccountMismatch
         );
 
@@ -885,12 +1721,9 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
-        //check pdaReceiver
-        let chain_id_seed = chain_id_stored.to_be_bytes();
-        let receiver_derived_pubkey: (Pubkey, u8) =
-            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        //check receiver, the data account keeps the receiver kind picked at start
         require!(
-            pda_receiver_passed == receiver_derived_pubkey.0,
+            is_receiver_account(&pda_receiver_passed, &receiver_stored, from_chain_id),
             MessengerError::ReceiverDerivedKeyMismatch
         );
 
@@ -907,6 +1740,16 @@ pub mod solana_project {
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
         zebec::cpi::pause_resume_token_stream(cpi_ctx)?;
+
+        emit!(PausedResumed {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
         Ok(())
     }
 
@@ -915,35 +1758,21 @@ pub mod solana_project {
         sender: [u8; 32],
         from_chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extract and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
-
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
-
-        require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
-        );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
-        require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
-        );
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let payload = decode_xstream_cancel(vaa.payload);
+        let payload = XstreamCancelPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check Mint passed
         let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
@@ -955,7 +1784,8 @@ pub mod solana_project {
         //check data account
         let data_account_passed: Pubkey = ctx.accounts.data_account.key();
         require!(
-            data_account_passed == Pubkey::new(&payload.data_account),
+            data_account_passed
+                == stream_data_account(&payload.depositor, from_chain_id, payload.stream_id).0,
             MessengerError::DataAccountMismatch
         );
 
@@ -978,13 +1808,9 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
-        //check pdaReceiver
-        let chain_id_stored = from_chain_id;
-        let chain_id_seed = chain_id_stored.to_be_bytes();
-        let receiver_derived_pubkey: (Pubkey, u8) =
-            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        //check receiver, the data account keeps the receiver kind picked at start
         require!(
-            pda_receiver_passed == receiver_derived_pubkey.0,
+            is_receiver_account(&pda_receiver_passed, &receiver_stored, from_chain_id),
             MessengerError::ReceiverDerivedKeyMismatch
         );
 
@@ -1012,6 +1838,32 @@ pub mod solana_project {
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
         zebec::cpi::cancel_token_stream(cpi_ctx)?;
+
+        emit!(CancelCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+
+        let report = StreamCancelledPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            depositor: payload.depositor,
+            receiver: payload.receiver,
+            token_mint: payload.token_mint,
+            data_account: ctx.accounts.data_account.key().to_bytes(),
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
         Ok(())
     }
 
@@ -1020,35 +1872,21 @@ pub mod solana_project {
         sender: [u8; 32],
         from_chain_id: u16,
     ) -> Result<()> {
-        //Hash a VAA Extracts and derive a VAA Key
-        let vaa = PostedMessageData::try_from_slice(&ctx.accounts.core_bridge_vaa.data.borrow())?.0;
-        let serialized_vaa = serialize_vaa(&vaa);
-
-        let mut h = sha3::Keccak256::default();
-        h.write_all(serialized_vaa.as_slice()).unwrap();
-        let vaa_hash: [u8; 32] = h.finalize().into();
-
-        let vaa_key = Pubkey::find_program_address(
-            &[b"PostedVAA", &vaa_hash],
-            &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
-        )
-        .0;
-
-        require!(
-            ctx.accounts.core_bridge_vaa.key() == vaa_key,
-            MessengerError::VAAKeyMismatch
-        );
-
-        // Already checked that the SignedVaa is owned by core bridge in account constraint logic
-        // Check that the emitter chain and address match up with the vaa
-        require!(
-            vaa.emitter_chain == ctx.accounts.emitter_acc.chain_id
-                && vaa.emitter_address
-                    == decode(ctx.accounts.emitter_acc.emitter_addr.as_str()).unwrap()[..],
-            MessengerError::VAAEmitterMismatch
-        );
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
 
-        let payload = decode_xstream_instant(vaa.payload);
+        let payload = XstreamInstantTransferPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
 
         //check Mint passed
         let mint_pubkey_passed: Pubkey = ctx.accounts.mint.key();
@@ -1057,6 +1895,10 @@ pub mod solana_project {
             MessengerError::MintKeyMismatch
         );
 
+        //scale amounts to the mint decimals
+        let amount =
+            denormalize_amount(payload.amount, payload.decimals, ctx.accounts.mint.decimals)?;
+
         //check sender
         let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
         let sender_stored = payload.sender;
@@ -1076,9 +1918,703 @@ pub mod solana_project {
             MessengerError::SenderDerivedKeyMismatch
         );
 
-        //check pdaReceiver
-        let chain_id_stored = from_chain_id;
-        let chain_id_seed = chain_id_stored.to_be_bytes();
+        //check receiver
+        require!(
+            pda_receiver_passed
+                == receiver_account(&receiver_stored, payload.receiver_kind, from_chain_id),
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::TokenInstantTransfer {
+            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
+            dest_account: ctx.accounts.dest_account.to_account_info(),
+            source_account: ctx.accounts.source_account.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            pda_account_token_account: ctx.accounts.pda_account_token_account.to_account_info(),
+            dest_token_account: ctx.accounts.dest_token_account.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::instant_token_transfer(cpi_ctx, amount)?;
+
+        emit!(InstantTransferCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(ctx.accounts.mint.key()),
+            amount,
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+        Ok(())
+    }
+
+    // Native SOL methods, zebec's SOL stream instructions funded from the sender PDA's lamports
+    pub fn xstream_native_start(
+        ctx: Context<XstreamNativeStart>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamNativeStartPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //scale amounts to lamports
+        let amount = denormalize_amount(payload.amount, payload.decimals, SOL_DECIMALS)?;
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.sender;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check receiver
+        let pda_receiver_passed: Pubkey = ctx.accounts.dest_account.key();
+        let receiver_stored = payload.receiver;
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        //check pdaReceiver
+        let receiver_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_receiver_passed == receiver_derived_pubkey.0,
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        create_stream_data_account(
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.data_account.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &sender,
+            from_chain_id,
+            payload.stream_id,
+            STREAM_SIZE,
+        )?;
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::Initialize {
+            data_account: ctx.accounts.data_account.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            fee_owner: ctx.accounts.fee_owner.to_account_info(),
+            fee_vault_data: ctx.accounts.fee_vault_data.to_account_info(),
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            receiver: ctx.accounts.dest_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::native_stream(
+            cpi_ctx,
+            payload.start_time,
+            payload.end_time,
+            amount,
+            payload.can_cancel == 1,
+            payload.can_update == 1,
+        )?;
+
+        emit!(StreamCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            amount,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        });
+
+        let report = StreamCreatedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            sender: payload.sender,
+            receiver: payload.receiver,
+            token_mint: NATIVE_MINT_BYTES,
+            data_account: ctx.accounts.data_account.key().to_bytes(),
+            amount,
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
+        Ok(())
+    }
+
+    pub fn xstream_native_update(
+        ctx: Context<XstreamNativeUpdate>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamNativeUpdatePayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //scale amounts to lamports
+        let amount = denormalize_amount(payload.amount, payload.decimals, SOL_DECIMALS)?;
+
+        //check data account
+        let data_account_passed: Pubkey = ctx.accounts.data_account.key();
+        require!(
+            data_account_passed
+                == stream_data_account(&payload.sender, from_chain_id, payload.stream_id).0,
+            MessengerError::DataAccountMismatch
+        );
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.sender;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check receiver
+        let pda_receiver_passed: Pubkey = ctx.accounts.dest_account.key();
+        let receiver_stored = payload.receiver;
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        //check pdaReceiver
+        let receiver_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_receiver_passed == receiver_derived_pubkey.0,
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::StreamUpdate {
+            data_account: ctx.accounts.data_account.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            receiver: ctx.accounts.dest_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::native_stream_update(cpi_ctx, payload.start_time, payload.end_time, amount)?;
+
+        emit!(StreamUpdated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            amount,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        });
+        Ok(())
+    }
+
+    pub fn xstream_native_deposit(
+        ctx: Context<XstreamNativeDeposit>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamNativeDepositPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //scale amounts to lamports
+        let amount = denormalize_amount(payload.amount, payload.decimals, SOL_DECIMALS)?;
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.sender;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::InitializeMasterPda {
+            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::deposit_sol(cpi_ctx, amount)?;
+
+        emit!(Deposited {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            amount,
+            sender: ctx.accounts.source_account.key(),
+        });
+        Ok(())
+    }
+
+    pub fn xstream_native_sender_withdraw(
+        ctx: Context<XstreamNativeSenderWithdraw>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload =
+            XstreamNativeWithdrawDepositPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //scale amounts to lamports
+        let amount = denormalize_amount(payload.amount, payload.decimals, SOL_DECIMALS)?;
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.withdrawer;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::InitializerWithdrawal {
+            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::native_withdrawal(cpi_ctx, amount)?;
+
+        emit!(SenderWithdrawCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            amount,
+            sender: ctx.accounts.source_account.key(),
+        });
+        Ok(())
+    }
+
+    pub fn xstream_native_withdraw(
+        ctx: Context<XstreamNativeWithdraw>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamNativeWithdrawPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //check data account
+        let data_account_passed: Pubkey = ctx.accounts.data_account.key();
+        require!(
+            data_account_passed
+                == stream_data_account(&payload.depositor, from_chain_id, payload.stream_id).0,
+            MessengerError::DataAccountMismatch
+        );
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.depositor;
+
+        //check receiver
+        let pda_receiver_passed: Pubkey = ctx.accounts.dest_account.key();
+        let receiver_stored = payload.withdrawer;
+        require!(
+            sender == receiver_stored,
+            MessengerError::PdaReceiverMismatch
+        );
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender_stored, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        //check pdaReceiver
+        let receiver_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_receiver_passed == receiver_derived_pubkey.0,
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::Withdraw {
+            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            receiver: ctx.accounts.dest_account.to_account_info(),
+            data_account: ctx.accounts.data_account.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            fee_owner: ctx.accounts.fee_owner.to_account_info(),
+            fee_vault_data: ctx.accounts.fee_vault_data.to_account_info(),
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("dest_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::withdraw_stream(cpi_ctx)?;
+
+        emit!(ReceiverWithdrawCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+
+        let report = WithdrawalCompletedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            withdrawer: payload.withdrawer,
+            depositor: payload.depositor,
+            token_mint: NATIVE_MINT_BYTES,
+            data_account: ctx.accounts.data_account.key().to_bytes(),
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
+        Ok(())
+    }
+
+    pub fn xstream_native_pause(
+        ctx: Context<XstreamNativePause>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamNativePausePayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //check data account
+        let data_account_passed: Pubkey = ctx.accounts.data_account.key();
+        require!(
+            data_account_passed
+                == stream_data_account(&payload.depositor, from_chain_id, payload.stream_id).0,
+            MessengerError::DataAccountMismatch
+        );
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.depositor;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check receiver
+        let pda_receiver_passed: Pubkey = ctx.accounts.dest_account.key();
+        let receiver_stored = payload.receiver;
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        //check pdaReceiver
+        let receiver_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_receiver_passed == receiver_derived_pubkey.0,
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::Pause {
+            sender: ctx.accounts.source_account.to_account_info(),
+            receiver: ctx.accounts.dest_account.to_account_info(),
+            data_account: ctx.accounts.data_account.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::pause_resume_stream(cpi_ctx)?;
+
+        emit!(PausedResumed {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+        Ok(())
+    }
+
+    pub fn xstream_native_cancel(
+        ctx: Context<XstreamNativeCancel>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload = XstreamNativeCancelPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //check data account
+        let data_account_passed: Pubkey = ctx.accounts.data_account.key();
+        require!(
+            data_account_passed
+                == stream_data_account(&payload.depositor, from_chain_id, payload.stream_id).0,
+            MessengerError::DataAccountMismatch
+        );
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.depositor;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check receiver
+        let pda_receiver_passed: Pubkey = ctx.accounts.dest_account.key();
+        let receiver_stored = payload.receiver;
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        //check pdaReceiver
+        let receiver_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_receiver_passed == receiver_derived_pubkey.0,
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::Cancel {
+            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            receiver: ctx.accounts.dest_account.to_account_info(),
+            data_account: ctx.accounts.data_account.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            fee_owner: ctx.accounts.fee_owner.to_account_info(),
+            fee_vault_data: ctx.accounts.fee_vault_data.to_account_info(),
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::cancel_stream(cpi_ctx)?;
+
+        emit!(CancelCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            data_account: ctx.accounts.data_account.key(),
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+
+        let report = StreamCancelledPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            depositor: payload.depositor,
+            receiver: payload.receiver,
+            token_mint: NATIVE_MINT_BYTES,
+            data_account: ctx.accounts.data_account.key().to_bytes(),
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
+        Ok(())
+    }
+
+    pub fn native_instant_transfer(
+        ctx: Context<XstreamNativeInstant>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let vaa = verify_vaa(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            from_chain_id,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let payload =
+            XstreamNativeInstantTransferPayload::deserialize(&mut vaa.payload.as_slice())?;
+        check_to_chain_id(&payload, &ctx.accounts.config)?;
+
+        //scale amounts to lamports
+        let amount = denormalize_amount(payload.amount, payload.decimals, SOL_DECIMALS)?;
+
+        //check sender
+        let pda_sender_passed: Pubkey = ctx.accounts.source_account.key();
+        let sender_stored = payload.sender;
+        require!(sender == sender_stored, MessengerError::PdaSenderMismatch);
+
+        //check receiver
+        let pda_receiver_passed: Pubkey = ctx.accounts.dest_account.key();
+        let receiver_stored = payload.receiver;
+
+        //check pdaSender
+        let chain_id_seed = from_chain_id.to_be_bytes();
+        let sender_derived_pubkey: (Pubkey, u8) =
+            Pubkey::find_program_address(&[&sender, &chain_id_seed], ctx.program_id);
+        require!(
+            pda_sender_passed == sender_derived_pubkey.0,
+            MessengerError::SenderDerivedKeyMismatch
+        );
+
+        //check pdaReceiver
         let receiver_derived_pubkey: (Pubkey, u8) =
             Pubkey::find_program_address(&[&receiver_stored, &chain_id_seed], ctx.program_id);
         require!(
@@ -1086,31 +2622,403 @@ pub mod solana_project {
             MessengerError::ReceiverDerivedKeyMismatch
         );
 
-        let zebec_program = ctx.accounts.zebec_program.to_account_info();
-        let zebec_accounts = zebec::cpi::accounts::TokenInstantTransfer {
-            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
-            dest_account: ctx.accounts.dest_account.to_account_info(),
-            source_account: ctx.accounts.source_account.to_account_info(),
-            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            pda_account_token_account: ctx.accounts.pda_account_token_account.to_account_info(),
-            dest_token_account: ctx.accounts.dest_token_account.to_account_info(),
-        };
-        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
-        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
-        zebec::cpi::instant_token_transfer(cpi_ctx, payload.amount)?;
-        Ok(())
+        let zebec_program = ctx.accounts.zebec_program.to_account_info();
+        let zebec_accounts = zebec::cpi::accounts::InstantTransfer {
+            zebec_vault: ctx.accounts.zebec_vault.to_account_info(),
+            sender: ctx.accounts.source_account.to_account_info(),
+            receiver: ctx.accounts.dest_account.to_account_info(),
+            withdraw_data: ctx.accounts.withdraw_data.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(zebec_program, zebec_accounts, signer_seeds);
+        zebec::cpi::instant_native_transfer(cpi_ctx, amount)?;
+
+        emit!(InstantTransferCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: None,
+            amount,
+            sender: ctx.accounts.source_account.key(),
+            receiver: ctx.accounts.dest_account.key(),
+        });
+        Ok(())
+    }
+
+    // Bridge-in methods, a single Token Bridge transfer with payload delivers the tokens and the
+    // DepositToken or TokenStream message that uses them
+    pub fn xstream_bridge_in_native(
+        ctx: Context<XstreamBridgeInNative>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let (vaa, message) = parse_bridge_in(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            &sender,
+            from_chain_id,
+            &ctx.accounts.source_account.key(),
+            &ctx.accounts.mint.key(),
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+
+        let balance_before = ctx.accounts.source_account_token_account.amount;
+
+        // Redeem the transfer into the sender PDA's token account, the PDA signs as redeemer
+        let complete_ix = Instruction {
+            program_id: ctx.accounts.portal_bridge_program.key(),
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.payer.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.portal_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.core_bridge_vaa.key(), false),
+                AccountMeta::new(ctx.accounts.portal_claim.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.portal_endpoint.key(), false),
+                AccountMeta::new(ctx.accounts.source_account_token_account.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.source_account.key(), true),
+                AccountMeta::new(ctx.accounts.portal_fee_recipient.key(), false),
+                AccountMeta::new(ctx.accounts.portal_custody.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.mint.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.portal_custody_signer.key(), false),
+                // Dependencies
+                AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+                // Program
+                AccountMeta::new_readonly(ctx.accounts.core_bridge_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            data: crate::portal::Instruction::CompleteNativeWithPayload.try_to_vec()?,
+        };
+
+        let complete_accs = vec![
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.portal_config.to_account_info(),
+            ctx.accounts.core_bridge_vaa.to_account_info(),
+            ctx.accounts.portal_claim.to_account_info(),
+            ctx.accounts.portal_endpoint.to_account_info(),
+            ctx.accounts.source_account_token_account.to_account_info(),
+            ctx.accounts.source_account.to_account_info(),
+            ctx.accounts.portal_fee_recipient.to_account_info(),
+            ctx.accounts.portal_custody.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.portal_custody_signer.to_account_info(),
+            // Dependencies
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            // Program
+            ctx.accounts.core_bridge_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ];
+
+        invoke_signed(&complete_ix, &complete_accs, signer_seeds)?;
+
+        ctx.accounts.source_account_token_account.reload()?;
+        let received = ctx
+            .accounts
+            .source_account_token_account
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(MessengerError::Overflow)?;
+
+        let report = execute_bridge_in(
+            &ctx.accounts.zebec,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.source_account.to_account_info(),
+            ctx.accounts.source_account_token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            signer_seeds,
+            &vaa,
+            &message,
+            ctx.accounts.mint.decimals,
+            received,
+        )?;
+        if let Some(report) = report {
+            post_message(
+                &ctx.accounts.outbound,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &mut ctx.accounts.config,
+                report.try_to_vec()?,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn xstream_bridge_in_wrapped(
+        ctx: Context<XstreamBridgeInWrapped>,
+        sender: [u8; 32],
+        from_chain_id: u16,
+    ) -> Result<()> {
+        let (vaa, message) = parse_bridge_in(
+            &ctx.accounts.core_bridge_vaa,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+            &sender,
+            from_chain_id,
+            &ctx.accounts.source_account.key(),
+            &ctx.accounts.mint.key(),
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let bump = ctx.bumps.get("source_account").unwrap().to_le_bytes();
+        let seeds: &[&[_]] = &[&sender, &from_chain_id.to_be_bytes(), bump.as_ref()];
+        let signer_seeds = &[&seeds[..]];
+
+        let balance_before = ctx.accounts.source_account_token_account.amount;
+
+        // Redeem the transfer into the sender PDA's token account, the PDA signs as redeemer
+        let complete_ix = Instruction {
+            program_id: ctx.accounts.portal_bridge_program.key(),
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.payer.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.portal_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.core_bridge_vaa.key(), false),
+                AccountMeta::new(ctx.accounts.portal_claim.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.portal_endpoint.key(), false),
+                AccountMeta::new(ctx.accounts.source_account_token_account.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.source_account.key(), true),
+                AccountMeta::new(ctx.accounts.portal_fee_recipient.key(), false),
+                AccountMeta::new(ctx.accounts.mint.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.wrapped_meta.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.portal_mint_authority.key(), false),
+                // Dependencies
+                AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+                // Program
+                AccountMeta::new_readonly(ctx.accounts.core_bridge_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            data: crate::portal::Instruction::CompleteWrappedWithPayload.try_to_vec()?,
+        };
+
+        let complete_accs = vec![
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.portal_config.to_account_info(),
+            ctx.accounts.core_bridge_vaa.to_account_info(),
+            ctx.accounts.portal_claim.to_account_info(),
+            ctx.accounts.portal_endpoint.to_account_info(),
+            ctx.accounts.source_account_token_account.to_account_info(),
+            ctx.accounts.source_account.to_account_info(),
+            ctx.accounts.portal_fee_recipient.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.wrapped_meta.to_account_info(),
+            ctx.accounts.portal_mint_authority.to_account_info(),
+            // Dependencies
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            // Program
+            ctx.accounts.core_bridge_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ];
+
+        invoke_signed(&complete_ix, &complete_accs, signer_seeds)?;
+
+        ctx.accounts.source_account_token_account.reload()?;
+        let received = ctx
+            .accounts
+            .source_account_token_account
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(MessengerError::Overflow)?;
+
+        let report = execute_bridge_in(
+            &ctx.accounts.zebec,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.source_account.to_account_info(),
+            ctx.accounts.source_account_token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            signer_seeds,
+            &vaa,
+            &message,
+            ctx.accounts.mint.decimals,
+            received,
+        )?;
+        if let Some(report) = report {
+            post_message(
+                &ctx.accounts.outbound,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &mut ctx.accounts.config,
+                report.try_to_vec()?,
+            )?;
+        }
+        Ok(())
+    }
+
+    // Consumes a permanently invalid VAA, such as one naming a wrong mint or a stream that no longer
+    // exists, without executing it, so relayers stop retrying it, and reports the failure back to
+    // its emitter chain. Left to the admin, as nothing here tells a valid VAA from an invalid one.
+    pub fn report_execution_failed(
+        ctx: Context<ReportExecutionFailed>,
+        error_code: u32,
+    ) -> Result<()> {
+        // No sender PDA is derived here, so only the emitter is checked
+        let vaa = parse_posted_vaa(&ctx.accounts.core_bridge_vaa)?;
+        check_emitter(
+            &vaa,
+            &vaa.emitter_address,
+            &ctx.accounts.emitter_acc,
+            &ctx.accounts.config,
+        )?;
+        record_vaa(
+            &vaa,
+            &mut ctx.accounts.sequence_bitmap,
+            &mut ctx.accounts.processed_vaa,
+            ctx.accounts.admin.key(),
+        )?;
+
+        ctx.accounts.processed_vaa.status = VaaStatus::Rejected;
+        ctx.accounts.processed_vaa.error_code = error_code;
+
+        emit!(VAARejected {
+            chain_id: vaa.emitter_chain,
+            emitter_addr: vaa.emitter_address,
+            sequence: vaa.sequence,
+            message_type: ctx.accounts.processed_vaa.message_type,
+            error_code: error_code
+        });
+
+        let report = ExecutionFailedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            message_type: ctx.accounts.processed_vaa.message_type,
+            error_code,
+        };
+        post_message(
+            &ctx.accounts.outbound,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.config,
+            report.try_to_vec()?,
+        )?;
+        Ok(())
+    }
+}
+
+fn transfer_wrapped(
+    ctx: Context<XstreamDirectTransferWrapped>,
+    sender: [u8; 32],
+    amount: u64,
+    sender_chain: u16,
+    target_chain: u16,
+    fee: u64,
+    receiver: [u8; 32],
+) -> Result<()> {
+    let bump = ctx.bumps.get("pda_signer").unwrap().to_le_bytes();
+
+    let signer_seeds: &[&[&[u8]]] = &[&[&sender, &sender_chain.to_be_bytes(), &bump]];
+
+    let approve_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Approve {
+            to: ctx.accounts.from.to_account_info(),
+            delegate: ctx.accounts.portal_authority_signer.to_account_info(),
+            authority: ctx.accounts.pda_signer.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    // Delgate transfer authority to Token Bridge for the tokens
+    approve(approve_ctx, amount)?;
+
+    let target_address: [u8; 32] = receiver.as_slice().try_into().unwrap();
+    // Instruction
+    let transfer_ix = Instruction {
+        program_id: Pubkey::from_str(TOKEN_BRIDGE_ADDRESS).unwrap(),
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.zebec_eoa.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.portal_config.key(), false),
+            AccountMeta::new(ctx.accounts.from.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.pda_signer.key(), true),
+            AccountMeta::new(ctx.accounts.wrapped_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.wrapped_meta.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.portal_authority_signer.key(), false),
+            AccountMeta::new(ctx.accounts.bridge_config.key(), false),
+            AccountMeta::new(ctx.accounts.portal_message.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.portal_emitter.key(), false),
+            AccountMeta::new(ctx.accounts.portal_sequence.key(), false),
+            AccountMeta::new(ctx.accounts.bridge_fee_collector.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            // Dependencies
+            AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            // Program
+            AccountMeta::new_readonly(ctx.accounts.core_bridge_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+        ],
+        data: (
+            crate::portal::Instruction::TransferWrapped,
+            TransferWrappedData {
+                nonce: ctx.accounts.config.nonce,
+                amount,
+                fee,
+                target_address,
+                target_chain,
+            },
+        )
+            .try_to_vec()?,
+    };
+
+    // Accounts
+    let transfer_accs = vec![
+        ctx.accounts.zebec_eoa.to_account_info(),
+        ctx.accounts.portal_config.to_account_info(),
+        ctx.accounts.from.to_account_info(),
+        ctx.accounts.pda_signer.to_account_info(),
+        ctx.accounts.wrapped_mint.to_account_info(),
+        ctx.accounts.wrapped_meta.to_account_info(),
+        ctx.accounts.portal_authority_signer.to_account_info(),
+        ctx.accounts.bridge_config.to_account_info(),
+        ctx.accounts.portal_message.to_account_info(),
+        ctx.accounts.portal_emitter.to_account_info(),
+        ctx.accounts.portal_sequence.to_account_info(),
+        ctx.accounts.bridge_fee_collector.to_account_info(),
+        ctx.accounts.clock.to_account_info(),
+        // Dependencies
+        ctx.accounts.rent.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        // Program
+        ctx.accounts.core_bridge_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    ];
+
+    invoke_signed(&transfer_ix, &transfer_accs, signer_seeds)?;
+
+    let sum = ctx.accounts.config.nonce.checked_add(1);
+    match sum {
+        None => return Err(MessengerError::Overflow.into()),
+        Some(val) => ctx.accounts.config.nonce = val,
     }
+
+    Ok(())
 }
 
-fn transfer_wrapped(
-    ctx: Context<XstreamDirectTransferWrapped>,
+//transfer
+fn transfer_native(
+    ctx: Context<XstreamDirectTransferNative>,
     sender: [u8; 32],
     amount: u64,
     sender_chain: u16,
@@ -1118,11 +3026,6 @@ fn transfer_wrapped(
     fee: u64,
     receiver: [u8; 32],
 ) -> Result<()> {
-    //Check EOA
-    require!(
-        ctx.accounts.config.owner == ctx.accounts.zebec_eoa.key(),
-        MessengerError::InvalidCaller
-    );
     let bump = ctx.bumps.get("pda_signer").unwrap().to_le_bytes();
 
     let signer_seeds: &[&[&[u8]]] = &[&[&sender, &sender_chain.to_be_bytes(), &bump]];
@@ -1141,6 +3044,124 @@ fn transfer_wrapped(
     approve(approve_ctx, amount)?;
 
     let target_address: [u8; 32] = receiver.as_slice().try_into().unwrap();
+    // Instruction
+    let transfer_ix = Instruction {
+        program_id: Pubkey::from_str(TOKEN_BRIDGE_ADDRESS).unwrap(),
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.zebec_eoa.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.portal_config.key(), false),
+            AccountMeta::new(ctx.accounts.from.key(), false),
+            AccountMeta::new(ctx.accounts.mint.key(), false),
+            AccountMeta::new(ctx.accounts.portal_custody.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.portal_authority_signer.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.portal_custody_signer.key(), false),
+            AccountMeta::new(ctx.accounts.bridge_config.key(), false),
+            AccountMeta::new(ctx.accounts.portal_message.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.portal_emitter.key(), false),
+            AccountMeta::new(ctx.accounts.portal_sequence.key(), false),
+            AccountMeta::new(ctx.accounts.bridge_fee_collector.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            // Dependencies
+            AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            // Program
+            AccountMeta::new_readonly(ctx.accounts.core_bridge_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+        ],
+        data: (
+            crate::portal::Instruction::TransferNative,
+            TransferNativeData {
+                nonce: ctx.accounts.config.nonce,
+                amount,
+                fee,
+                target_address,
+                target_chain,
+            },
+        )
+            .try_to_vec()?,
+    };
+
+    // Accounts
+    let transfer_accs = vec![
+        ctx.accounts.zebec_eoa.to_account_info(),
+        ctx.accounts.portal_config.to_account_info(),
+        ctx.accounts.from.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.portal_custody.to_account_info(),
+        ctx.accounts.portal_authority_signer.to_account_info(),
+        ctx.accounts.portal_custody_signer.to_account_info(),
+        ctx.accounts.bridge_config.to_account_info(),
+        ctx.accounts.portal_message.to_account_info(),
+        ctx.accounts.portal_emitter.to_account_info(),
+        ctx.accounts.portal_sequence.to_account_info(),
+        ctx.accounts.bridge_fee_collector.to_account_info(),
+        ctx.accounts.clock.to_account_info(),
+        // Dependencies
+        ctx.accounts.rent.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        // Program
+        ctx.accounts.core_bridge_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    ];
+
+    invoke_signed(&transfer_ix, &transfer_accs, signer_seeds)?;
+
+    let sum = ctx.accounts.config.nonce.checked_add(1);
+    match sum {
+        None => return Err(MessengerError::Overflow.into()),
+        Some(val) => ctx.accounts.config.nonce = val,
+    }
+
+    Ok(())
+}
+
+// Sends a Token Bridge transfer with payload to the target chain Messenger. The program's
+// `sender` PDA signs so the Messenger sees this program as the `from_address`.
+fn transfer_wrapped_with_payload(
+    ctx: Context<XstreamDirectTransferWrappedWithPayload>,
+    sender: [u8; 32],
+    amount: u64,
+    sender_chain: u16,
+    target_chain: u16,
+    fee: u64,
+    memo: TransferMemoPayload,
+) -> Result<()> {
+    let bump = ctx.bumps.get("pda_signer").unwrap().to_le_bytes();
+    let sender_bump = ctx.bumps.get("program_sender").unwrap().to_le_bytes();
+
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[&sender, &sender_chain.to_be_bytes(), &bump],
+        &[b"sender", &sender_bump],
+    ];
+
+    if fee > 0 {
+        let fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferToken {
+                from: ctx.accounts.from.to_account_info(),
+                to: ctx.accounts.relayer_token_account.to_account_info(),
+                authority: ctx.accounts.pda_signer.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        // Pay the relayer, the Token Bridge has no fee for transfers with payload
+        transfer_token(fee_ctx, fee)?;
+    }
+
+    let approve_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Approve {
+            to: ctx.accounts.from.to_account_info(),
+            delegate: ctx.accounts.portal_authority_signer.to_account_info(),
+            authority: ctx.accounts.pda_signer.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    // Delgate transfer authority to Token Bridge for the tokens
+    approve(approve_ctx, amount)?;
+
     // Instruction
     let transfer_ix = Instruction {
         program_id: Pubkey::from_str(TOKEN_BRIDGE_ADDRESS).unwrap(),
@@ -1158,6 +3179,7 @@ fn transfer_wrapped(
             AccountMeta::new(ctx.accounts.portal_sequence.key(), false),
             AccountMeta::new(ctx.accounts.bridge_fee_collector.key(), false),
             AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.program_sender.key(), true),
             // Dependencies
             AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
             AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
@@ -1166,13 +3188,14 @@ fn transfer_wrapped(
             AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
         ],
         data: (
-            crate::portal::Instruction::TransferWrapped,
-            TransferWrappedData {
+            crate::portal::Instruction::TransferWrappedWithPayload,
+            TransferWrappedWithPayloadData {
                 nonce: ctx.accounts.config.nonce,
                 amount,
-                fee,
-                target_address,
+                target_address: ctx.accounts.target_emitter_acc.emitter_addr,
                 target_chain,
+                payload: memo.try_to_vec()?,
+                cpi_program_id: Some(crate::ID),
             },
         )
             .try_to_vec()?,
@@ -1193,6 +3216,7 @@ fn transfer_wrapped(
         ctx.accounts.portal_sequence.to_account_info(),
         ctx.accounts.bridge_fee_collector.to_account_info(),
         ctx.accounts.clock.to_account_info(),
+        ctx.accounts.program_sender.to_account_info(),
         // Dependencies
         ctx.accounts.rent.to_account_info(),
         ctx.accounts.system_program.to_account_info(),
@@ -1212,25 +3236,38 @@ fn transfer_wrapped(
     Ok(())
 }
 
-//transfer
-fn transfer_native(
-    ctx: Context<XstreamDirectTransferNative>,
+// Native counterpart of `transfer_wrapped_with_payload`
+fn transfer_native_with_payload(
+    ctx: Context<XstreamDirectTransferNativeWithPayload>,
     sender: [u8; 32],
     amount: u64,
     sender_chain: u16,
     target_chain: u16,
     fee: u64,
-    receiver: [u8; 32],
+    memo: TransferMemoPayload,
 ) -> Result<()> {
-    //Check EOA
-    require!(
-        ctx.accounts.config.owner == ctx.accounts.zebec_eoa.key(),
-        MessengerError::InvalidCaller
-    );
-
     let bump = ctx.bumps.get("pda_signer").unwrap().to_le_bytes();
+    let sender_bump = ctx.bumps.get("program_sender").unwrap().to_le_bytes();
 
-    let signer_seeds: &[&[&[u8]]] = &[&[&sender, &sender_chain.to_be_bytes(), &bump]];
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[&sender, &sender_chain.to_be_bytes(), &bump],
+        &[b"sender", &sender_bump],
+    ];
+
+    if fee > 0 {
+        let fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferToken {
+                from: ctx.accounts.from.to_account_info(),
+                to: ctx.accounts.relayer_token_account.to_account_info(),
+                authority: ctx.accounts.pda_signer.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        // Pay the relayer, the Token Bridge has no fee for transfers with payload
+        transfer_token(fee_ctx, fee)?;
+    }
 
     let approve_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -1245,7 +3282,6 @@ fn transfer_native(
     // Delgate transfer authority to Token Bridge for the tokens
     approve(approve_ctx, amount)?;
 
-    let target_address: [u8; 32] = receiver.as_slice().try_into().unwrap();
     // Instruction
     let transfer_ix = Instruction {
         program_id: Pubkey::from_str(TOKEN_BRIDGE_ADDRESS).unwrap(),
@@ -1263,6 +3299,7 @@ fn transfer_native(
             AccountMeta::new(ctx.accounts.portal_sequence.key(), false),
             AccountMeta::new(ctx.accounts.bridge_fee_collector.key(), false),
             AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.program_sender.key(), true),
             // Dependencies
             AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
             AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
@@ -1271,13 +3308,14 @@ fn transfer_native(
             AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
         ],
         data: (
-            crate::portal::Instruction::TransferNative,
-            TransferNativeData {
+            crate::portal::Instruction::TransferNativeWithPayload,
+            TransferNativeWithPayloadData {
                 nonce: ctx.accounts.config.nonce,
                 amount,
-                fee,
-                target_address,
+                target_address: ctx.accounts.target_emitter_acc.emitter_addr,
                 target_chain,
+                payload: memo.try_to_vec()?,
+                cpi_program_id: Some(crate::ID),
             },
         )
             .try_to_vec()?,
@@ -1298,6 +3336,7 @@ fn transfer_native(
         ctx.accounts.portal_sequence.to_account_info(),
         ctx.accounts.bridge_fee_collector.to_account_info(),
         ctx.accounts.clock.to_account_info(),
+        ctx.accounts.program_sender.to_account_info(),
         // Dependencies
         ctx.accounts.rent.to_account_info(),
         ctx.accounts.system_program.to_account_info(),
@@ -1317,201 +3356,374 @@ fn transfer_native(
     Ok(())
 }
 
-fn get_u64(data_bytes: Vec<u8>) -> u64 {
-    let data_u8 = <[u8; 8]>::try_from(data_bytes).unwrap();
-    u64::from_be_bytes(data_u8)
-}
-
-fn get_u256(data_bytes: Vec<u8>) -> U256 {
-    let data_u8 = <[u8; 32]>::try_from(data_bytes).unwrap();
-    U256::from_big_endian(&data_u8)
+// Sends `amount` lamports from the relayer to a sender PDA and records the sponsorship
+fn fund_pda<'info>(
+    zebec_eoa: &Signer<'info>,
+    pda_account: &UncheckedAccount<'info>,
+    pda_sponsorship: &mut Account<'info, PdaSponsorship>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_transfer_sol = TransferSol {
+        from: zebec_eoa.to_account_info(),
+        to: pda_account.to_account_info(),
+    };
+    let cpi_transfer_sol_ctx = CpiContext::new(system_program.to_account_info(), cpi_transfer_sol);
+    transfer_sol(cpi_transfer_sol_ctx, amount)?;
+
+    pda_sponsorship.pda = pda_account.key();
+    pda_sponsorship.sponsored = pda_sponsorship
+        .sponsored
+        .checked_add(amount)
+        .ok_or(MessengerError::Overflow)?;
+
+    emit!(PdaFunded {
+        pda: pda_account.key(),
+        amount: amount,
+        sponsored: pda_sponsorship.sponsored
+    });
+    Ok(())
 }
 
-fn get_u8(data_bytes: Vec<u8>) -> u64 {
-    let prefix_bytes = vec![0; 7];
-    let joined_bytes = [prefix_bytes, data_bytes].concat();
-    let data_u8 = <[u8; 8]>::try_from(joined_bytes).unwrap();
-    u64::from_be_bytes(data_u8)
-}
+// Reads a Token Bridge transfer with payload sent by a registered Messenger to the sender PDA and
+// returns the xstream message it carries
+fn parse_bridge_in(
+    core_bridge_vaa: &AccountInfo,
+    emitter_acc: &EmitterAddrAccount,
+    config: &Config,
+    sender: &[u8; 32],
+    from_chain_id: u16,
+    redeemer: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(ParsedVaa, XstreamMessage)> {
+    let vaa = parse_posted_vaa(core_bridge_vaa)?;
+    let transfer = PayloadTransferWithPayload::deserialize(&mut vaa.payload.as_slice())?;
+    check_emitter(&vaa, &transfer.from_address, emitter_acc, config)?;
+    require!(
+        vaa.emitter_chain == from_chain_id,
+        MessengerError::VAAEmitterMismatch
+    );
+    require!(
+        Pubkey::new(&transfer.to) == *redeemer,
+        MessengerError::RedeemerMismatch
+    );
 
-fn get_u32_array(data_bytes: Vec<u8>) -> [u8; 32] {
-    let data_result = data_bytes.try_into().unwrap();
-    return data_result;
-}
+    let message = XstreamMessage::deserialize(&mut transfer.payload.as_slice())?;
+    let (message_sender, token_mint) = match &message {
+        XstreamMessage::Deposit(payload) => {
+            check_to_chain_id(payload, config)?;
+            (payload.sender, payload.token_mint)
+        }
+        XstreamMessage::Start(payload) => {
+            check_to_chain_id(payload, config)?;
+            (payload.sender, payload.token_mint)
+        }
+        _ => return err!(MessengerError::UnsupportedBridgedMessage),
+    };
+    require!(*sender == message_sender, MessengerError::PdaSenderMismatch);
+    require!(
+        *mint == Pubkey::new(&token_mint),
+        MessengerError::MintKeyMismatch
+    );
 
-// Convert a full VAA structure into the serialization of its unique components, this structure is
-// what is hashed and verified by Guardians.
-pub fn serialize_vaa(vaa: &MessageData) -> Vec<u8> {
-    let mut v = Cursor::new(Vec::new());
-    v.write_u32::<BigEndian>(vaa.vaa_time).unwrap();
-    v.write_u32::<BigEndian>(vaa.nonce).unwrap();
-    v.write_u16::<BigEndian>(vaa.emitter_chain as u16).unwrap();
-    v.write_all(&vaa.emitter_address).unwrap();
-    v.write_u64::<BigEndian>(vaa.sequence).unwrap();
-    v.write_u8(vaa.consistency_level).unwrap();
-    v.write_all(&vaa.payload).unwrap();
-    v.into_inner()
+    Ok((vaa, message))
 }
 
-fn decode_xstream(encoded_str: Vec<u8>) -> XstreamStartPayload {
-    let start_time = get_u64(encoded_str[1..9].to_vec());
-    let end_time = get_u64(encoded_str[9..17].to_vec());
-    let amount = get_u64(encoded_str[17..25].to_vec());
-    let to_chain_id = get_u32_array(encoded_str[25..57].to_vec());
-    let sender = get_u32_array(encoded_str[57..89].to_vec());
-    let receiver = get_u32_array(encoded_str[89..121].to_vec());
-    let can_cancel = get_u64(encoded_str[121..129].to_vec());
-    let can_update = get_u64(encoded_str[129..137].to_vec());
-    let token_mint = get_u32_array(encoded_str[137..169].to_vec());
-
-    let stream_payload = XstreamStartPayload {
-        start_time,
-        end_time,
-        amount,
-        to_chain_id,
-        sender,
-        receiver,
-        can_update,
-        can_cancel,
-        token_mint,
+// Deposits the bridged tokens into zebec and, for TokenStream messages, starts the stream and
+// returns the StreamCreated report to post back to the source chain
+#[allow(clippy::too_many_arguments)]
+fn execute_bridge_in<'info>(
+    zebec: &BridgeInZebec<'info>,
+    payer: AccountInfo<'info>,
+    source_account: AccountInfo<'info>,
+    source_account_token_account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    rent: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    vaa: &ParsedVaa,
+    message: &XstreamMessage,
+    mint_decimals: u8,
+    received: u64,
+) -> Result<Option<StreamCreatedPayload>> {
+    let from_chain_id = vaa.emitter_chain;
+    let amount = match message {
+        XstreamMessage::Deposit(payload) => {
+            denormalize_amount(payload.amount, payload.decimals, mint_decimals)?
+        }
+        XstreamMessage::Start(payload) => {
+            denormalize_amount(payload.amount, payload.decimals, mint_decimals)?
+        }
+        _ => return err!(MessengerError::UnsupportedBridgedMessage),
     };
-    stream_payload
-}
+    require!(
+        amount <= received,
+        MessengerError::InsufficientBridgedAmount
+    );
 
-fn decode_xstream_withdraw(encoded_str: Vec<u8>) -> XstreamWithdrawPayload {
-    let to_chain_id = get_u32_array(encoded_str[1..33].to_vec());
-    let withdrawer = get_u32_array(encoded_str[33..65].to_vec());
-    let token_mint = get_u32_array(encoded_str[65..97].to_vec());
-    let depositor = get_u32_array(encoded_str[97..129].to_vec());
-    let data_account = get_u32_array(encoded_str[129..161].to_vec());
-
-    let payload = XstreamWithdrawPayload {
-        to_chain_id,
-        withdrawer,
-        token_mint,
-        depositor,
-        data_account,
+    let zebec_accounts = zebec::cpi::accounts::TokenDeposit {
+        zebec_vault: zebec.zebec_vault.to_account_info(),
+        source_account: source_account.clone(),
+        system_program: system_program.clone(),
+        token_program: token_program.clone(),
+        associated_token_program: zebec.associated_token_program.to_account_info(),
+        rent: rent.clone(),
+        mint: mint.clone(),
+        pda_account_token_account: zebec.pda_account_token_account.to_account_info(),
+        source_account_token_account,
     };
-    payload
-}
-
-fn decode_xstream_deposit(encoded_str: Vec<u8>) -> XstreamDepositPayload {
-    let amount = get_u64(encoded_str[1..9].to_vec());
-    let to_chain_id = get_u32_array(encoded_str[9..41].to_vec());
-    let sender = get_u32_array(encoded_str[41..73].to_vec());
-    let token_mint = get_u32_array(encoded_str[73..105].to_vec());
+    let cpi_ctx = CpiContext::new_with_signer(
+        zebec.zebec_program.to_account_info(),
+        zebec_accounts,
+        signer_seeds,
+    );
+    zebec::cpi::deposit_token(cpi_ctx, amount)?;
 
-    let payload = XstreamDepositPayload {
+    emit!(Deposited {
+        vaa_hash: vaa.hash,
+        emitter_chain: vaa.emitter_chain,
+        sequence: vaa.sequence,
+        token_mint: Some(mint.key()),
         amount,
-        to_chain_id,
-        sender,
-        token_mint,
-    };
-    payload
+        sender: source_account.key(),
+    });
+
+    if let XstreamMessage::Start(payload) = message {
+        //check receiver
+        require!(
+            zebec.dest_account.key()
+                == receiver_account(&payload.receiver, payload.receiver_kind, from_chain_id),
+            MessengerError::ReceiverDerivedKeyMismatch
+        );
+
+        create_stream_data_account(
+            payer,
+            zebec.data_account.to_account_info(),
+            system_program.clone(),
+            &payload.sender,
+            from_chain_id,
+            payload.stream_id,
+            STREAM_TOKEN_SIZE,
+        )?;
+
+        let sender_pda = source_account.key();
+        let token_mint = mint.key();
+        let zebec_accounts = zebec::cpi::accounts::TokenStream {
+            dest_account: zebec.dest_account.to_account_info(),
+            source_account,
+            fee_owner: zebec.fee_owner.to_account_info(),
+            fee_vault_data: zebec.fee_vault_data.to_account_info(),
+            fee_vault: zebec.fee_vault.to_account_info(),
+            data_account: zebec.data_account.to_account_info(),
+            withdraw_data: zebec.withdraw_data.to_account_info(),
+            system_program,
+            token_program,
+            rent,
+            mint,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            zebec.zebec_program.to_account_info(),
+            zebec_accounts,
+            signer_seeds,
+        );
+        zebec::cpi::token_stream(
+            cpi_ctx,
+            payload.start_time,
+            payload.end_time,
+            amount,
+            payload.can_cancel == 1,
+            payload.can_update == 1,
+        )?;
+
+        emit!(StreamCreated {
+            vaa_hash: vaa.hash,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            token_mint: Some(token_mint),
+            amount,
+            data_account: zebec.data_account.key(),
+            sender: sender_pda,
+            receiver: zebec.dest_account.key(),
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        });
+
+        return Ok(Some(StreamCreatedPayload {
+            to_chain_id: encode_chain_id(vaa.emitter_chain),
+            sequence: vaa.sequence,
+            sender: payload.sender,
+            receiver: payload.receiver,
+            token_mint: payload.token_mint,
+            data_account: zebec.data_account.key().to_bytes(),
+            amount,
+            start_time: payload.start_time,
+            end_time: payload.end_time,
+        }));
+    }
+
+    Ok(None)
 }
 
-fn decode_xstream_update(encoded_str: Vec<u8>) -> XstreamUpdatePayload {
-    let start_time = get_u64(encoded_str[1..9].to_vec());
-    let end_time = get_u64(encoded_str[9..17].to_vec());
-    let amount = get_u64(encoded_str[17..25].to_vec());
-    let to_chain_id = get_u32_array(encoded_str[25..57].to_vec());
-    let sender = get_u32_array(encoded_str[57..89].to_vec());
-    let receiver = get_u32_array(encoded_str[89..121].to_vec());
-    let token_mint = get_u32_array(encoded_str[121..153].to_vec());
-    let data_account = get_u32_array(encoded_str[153..185].to_vec());
-
-    let payload = XstreamUpdatePayload {
-        start_time,
-        end_time,
-        amount,
-        to_chain_id,
-        sender,
-        receiver,
-        token_mint,
-        data_account,
-    };
-    payload
+// FeeSchedule.max_fee in base units of a mint with `mint_decimals`. Rounded down and saturated,
+// as it is only an upper bound.
+fn scheduled_max_fee(max_fee: u64, mint_decimals: u8) -> u64 {
+    if mint_decimals >= TOKEN_BRIDGE_MAX_DECIMALS {
+        10u64
+            .saturating_pow((mint_decimals - TOKEN_BRIDGE_MAX_DECIMALS).into())
+            .saturating_mul(max_fee)
+    } else {
+        max_fee / 10u64.pow((TOKEN_BRIDGE_MAX_DECIMALS - mint_decimals).into())
+    }
 }
 
-fn decode_xstream_pause(encoded_str: Vec<u8>) -> XstreamPausePayload {
-    let to_chain_id = get_u32_array(encoded_str[1..33].to_vec());
-    let depositor = get_u32_array(encoded_str[33..65].to_vec());
-    let token_mint = get_u32_array(encoded_str[65..97].to_vec());
-    let receiver = get_u32_array(encoded_str[97..129].to_vec());
-    let data_account = get_u32_array(encoded_str[129..161].to_vec());
-
-    let payload = XstreamPausePayload {
-        to_chain_id,
-        depositor,
-        token_mint,
-        receiver,
-        data_account,
-    };
-    payload
+// Highest relayer fee allowed by `fee_schedule`, in base units of a mint with `mint_decimals`.
+// Chains without a schedule are only bound by the signed cap.
+fn fee_schedule_cap(fee_schedule: &AccountInfo, mint_decimals: u8) -> Result<u64> {
+    if fee_schedule.data_is_empty() {
+        return Ok(u64::MAX);
+    }
+    let fee_schedule = Account::<FeeSchedule>::try_from(fee_schedule)?;
+    Ok(scheduled_max_fee(fee_schedule.max_fee, mint_decimals))
 }
 
-fn decode_xstream_cancel(encoded_str: Vec<u8>) -> XstreamCancelPayload {
-    let to_chain_id = get_u32_array(encoded_str[1..33].to_vec());
-    let depositor = get_u32_array(encoded_str[33..65].to_vec());
-    let token_mint = get_u32_array(encoded_str[65..97].to_vec());
-    let receiver = get_u32_array(encoded_str[97..129].to_vec());
-    let data_account = get_u32_array(encoded_str[129..161].to_vec());
-
-    let payload = XstreamCancelPayload {
-        to_chain_id,
-        depositor,
-        token_mint,
-        receiver,
-        data_account,
-    };
-    payload
+// Solana account paid for `receiver` of `from_chain_id`
+fn receiver_account(receiver: &[u8; 32], kind: ReceiverKind, from_chain_id: u16) -> Pubkey {
+    match kind {
+        ReceiverKind::ChainPda => {
+            Pubkey::find_program_address(&[receiver, &from_chain_id.to_be_bytes()], &crate::ID).0
+        }
+        ReceiverKind::SolanaWallet => Pubkey::new(receiver),
+    }
 }
 
-fn decode_deposit_withdraw(encoded_str: Vec<u8>) -> XstreamWithdrawDepositPayload {
-    let amount = get_u64(encoded_str[1..9].to_vec());
-    let to_chain_id = get_u32_array(encoded_str[9..41].to_vec());
-    let withdrawer = get_u32_array(encoded_str[41..73].to_vec());
-    let token_mint = get_u32_array(encoded_str[73..105].to_vec());
+// Whether `account` is the receiver account of `receiver` for either receiver kind
+fn is_receiver_account(account: &Pubkey, receiver: &[u8; 32], from_chain_id: u16) -> bool {
+    [ReceiverKind::ChainPda, ReceiverKind::SolanaWallet]
+        .iter()
+        .any(|kind| receiver_account(receiver, *kind, from_chain_id) == *account)
+}
 
-    let payload = XstreamWithdrawDepositPayload {
-        amount,
-        to_chain_id,
-        withdrawer,
-        token_mint,
-    };
-    payload
+// Address of the zebec data account of a stream, known to the sender before it is started
+fn stream_data_account(sender: &[u8; 32], chain_id: u16, stream_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"stream",
+            sender,
+            &chain_id.to_be_bytes(),
+            &stream_id.to_be_bytes(),
+        ],
+        &crate::ID,
+    )
 }
 
-fn decode_xstream_instant(encoded_str: Vec<u8>) -> XstreamInstantTransferPayload {
-    let amount = get_u64(encoded_str[1..9].to_vec());
-    let to_chain_id = get_u32_array(encoded_str[9..41].to_vec());
-    let sender = get_u32_array(encoded_str[41..73].to_vec());
-    let token_mint = get_u32_array(encoded_str[73..105].to_vec());
-    let receiver = get_u32_array(encoded_str[105..137].to_vec());
+// Creates the stream data account of `size` bytes owned by zebec, ready for its `zero` constraint. An account
+// already holding lamports cannot be created, so it is topped up, allocated and assigned instead.
+fn create_stream_data_account<'info>(
+    payer: AccountInfo<'info>,
+    data_account: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    sender: &[u8; 32],
+    chain_id: u16,
+    stream_id: u64,
+    size: usize,
+) -> Result<()> {
+    let (data_account_pda, bump) = stream_data_account(sender, chain_id, stream_id);
+    require!(
+        data_account.key() == data_account_pda,
+        MessengerError::DataAccountMismatch
+    );
 
-    let payload = XstreamInstantTransferPayload {
-        amount,
-        to_chain_id,
+    let bump = bump.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"stream",
         sender,
-        token_mint,
-        receiver,
-    };
-    payload
+        &chain_id.to_be_bytes(),
+        &stream_id.to_be_bytes(),
+        &bump,
+    ];
+    let signer_seeds = &[seeds];
+
+    let rent_lamport = Rent::get()?.minimum_balance(size);
+    let lamports = data_account.lamports();
+    if lamports == 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            CreateAccount {
+                from: payer,
+                to: data_account,
+            },
+            signer_seeds,
+        );
+        return create_account(cpi_ctx, rent_lamport, size as u64, &zebec::ID);
+    }
+
+    if rent_lamport > lamports {
+        let cpi_ctx = CpiContext::new(
+            system_program.clone(),
+            TransferSol {
+                from: payer,
+                to: data_account.clone(),
+            },
+        );
+        transfer_sol(cpi_ctx, rent_lamport - lamports)?;
+    }
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Allocate {
+            account_to_allocate: data_account.clone(),
+        },
+        signer_seeds,
+    );
+    allocate(cpi_ctx, size as u64)?;
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program,
+        Assign {
+            account_to_assign: data_account,
+        },
+        signer_seeds,
+    );
+    assign(cpi_ctx, &zebec::ID)
 }
 
-fn decode_xstream_direct(encoded_str: Vec<u8>) -> XstreamDirectTransferPayload {
-    let amount = get_u64(encoded_str[1..9].to_vec());
-    let to_chain_id = get_u32_array(encoded_str[9..41].to_vec());
-    let sender = get_u32_array(encoded_str[41..73].to_vec());
-    let token_mint = get_u32_array(encoded_str[73..105].to_vec());
-    let receiver = get_u32_array(encoded_str[105..137].to_vec());
+// Scales an amount expressed in `decimals` to base units of a mint with `mint_decimals`. Amounts
+// that overflow a u64 or have digits below the smallest unit of the mint are rejected.
+fn denormalize_amount(
+    amount: u64,
+    decimals: u8,
+    mint_decimals: u8,
+) -> std::result::Result<u64, MessengerError> {
+    if decimals <= mint_decimals {
+        10u64
+            .checked_pow((mint_decimals - decimals).into())
+            .and_then(|factor| amount.checked_mul(factor))
+            .ok_or(MessengerError::Overflow)
+    } else {
+        match 10u64.checked_pow((decimals - mint_decimals).into()) {
+            Some(factor) if amount % factor == 0 => Ok(amount / factor),
+            None if amount == 0 => Ok(0),
+            _ => Err(MessengerError::AmountPrecisionLoss),
+        }
+    }
+}
 
-    let payload = XstreamDirectTransferPayload {
-        amount,
-        to_chain_id,
-        sender,
-        token_mint,
-        receiver,
+// Part of `amount` the Token Bridge carries for a mint with `mint_decimals`, without the digits
+// it would truncate past TOKEN_BRIDGE_MAX_DECIMALS.
+fn bridgeable_amount(amount: u64, mint_decimals: u8) -> Result<u64> {
+    let truncated_decimals = mint_decimals.saturating_sub(TOKEN_BRIDGE_MAX_DECIMALS);
+    let bridged_amount = match 10u64.checked_pow(truncated_decimals.into()) {
+        Some(factor) => amount - amount % factor,
+        None => 0,
     };
-    payload
+    require!(bridged_amount > 0, MessengerError::AmountBelowBridgeMinimum);
+    Ok(bridged_amount)
+}
+
+// Reject messages that were addressed to another chain or deployment
+fn check_to_chain_id<T: XstreamPayload>(payload: &T, config: &Config) -> Result<()> {
+    require!(
+        U256::from_big_endian(payload.to_chain_id()) == U256::from(config.local_chain_id),
+        MessengerError::InvalidToChainId
+    );
+    Ok(())
 }
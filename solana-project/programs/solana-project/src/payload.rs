@@ -1,6 +1,289 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use anchor_lang::prelude::*;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Read, Write};
 
-#[derive(BorshDeserialize, BorshSerialize)]
+use crate::constants::MAX_BATCH_SIZE;
+use crate::errors::MessengerError;
+use crate::portal::{DeserializePayload, SerializePayload};
+
+/// Version of the xstream wire format, written right after the message code.
+pub const PAYLOAD_VERSION: u8 = 1;
+
+/// Every xstream payload starts with a message code followed by the version byte.
+pub const PAYLOAD_HEADER_LENGTH: usize = 2;
+
+/// Registry of the message codes exchanged with the EVM Messenger, inbound stream actions and
+/// the outbound reports posted by this program. The discriminants must match the constants in
+/// Encoder.sol.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum MessageType {
+    NativeStream = 1,
+    TokenStream = 2,
+    NativeWithdrawStream = 3,
+    TokenWithdrawStream = 4,
+    DepositSol = 5,
+    DepositToken = 6,
+    PauseNative = 7,
+    PauseToken = 8,
+    WithdrawSol = 9,
+    WithdrawToken = 10,
+    InstantNative = 11,
+    InstantToken = 12,
+    NativeStreamUpdate = 13,
+    TokenStreamUpdate = 14,
+    CancelNative = 15,
+    CancelToken = 16,
+    DirectTransfer = 17,
+    InitializePda = 18,
+    InitializeTokenAccount = 19,
+    StreamCreated = 20,
+    StreamCancelled = 21,
+    WithdrawalCompleted = 22,
+    ExecutionFailed = 23,
+    TransferMemo = 24,
+    BatchTokenStream = 25,
+    BatchStreamsProcessed = 26,
+}
+
+impl MessageType {
+    /// Name of the instruction, or Messenger function for outbound reports, that consumes
+    /// messages of this type.
+    pub fn handler(&self) -> &'static str {
+        match self {
+            MessageType::NativeStream => "xstream_native_start",
+            MessageType::TokenStream => "xstream_start",
+            MessageType::BatchTokenStream => "xstream_batch_start",
+            MessageType::NativeWithdrawStream => "xstream_native_withdraw",
+            MessageType::TokenWithdrawStream => "xstream_withdraw",
+            MessageType::DepositSol => "xstream_native_deposit",
+            MessageType::DepositToken => "xstream_deposit",
+            MessageType::PauseNative => "xstream_native_pause",
+            MessageType::PauseToken => "xstream_pause",
+            MessageType::WithdrawSol => "xstream_native_sender_withdraw",
+            MessageType::WithdrawToken => "xstream_sender_withdraw",
+            MessageType::InstantNative => "native_instant_transfer",
+            MessageType::InstantToken => "instant_transfer",
+            MessageType::NativeStreamUpdate => "xstream_native_update",
+            MessageType::TokenStreamUpdate => "xstream_update",
+            MessageType::CancelNative => "xstream_native_cancel",
+            MessageType::CancelToken => "xstream_cancel",
+            MessageType::DirectTransfer => "xstream_direct_transfer_native/wrapped",
+            MessageType::InitializePda => "initialize_pda",
+            MessageType::InitializeTokenAccount => "initialize_pda_token_account",
+            MessageType::StreamCreated
+            | MessageType::StreamCancelled
+            | MessageType::WithdrawalCompleted
+            | MessageType::ExecutionFailed
+            | MessageType::BatchStreamsProcessed => "Messenger.receiveMessage",
+            MessageType::TransferMemo => "Messenger.receiveTransfer",
+        }
+    }
+}
+
+impl TryFrom<u8> for MessageType {
+    type Error = Error;
+
+    fn try_from(code: u8) -> Result<Self> {
+        let message_type = match code {
+            1 => MessageType::NativeStream,
+            2 => MessageType::TokenStream,
+            3 => MessageType::NativeWithdrawStream,
+            4 => MessageType::TokenWithdrawStream,
+            5 => MessageType::DepositSol,
+            6 => MessageType::DepositToken,
+            7 => MessageType::PauseNative,
+            8 => MessageType::PauseToken,
+            9 => MessageType::WithdrawSol,
+            10 => MessageType::WithdrawToken,
+            11 => MessageType::InstantNative,
+            12 => MessageType::InstantToken,
+            13 => MessageType::NativeStreamUpdate,
+            14 => MessageType::TokenStreamUpdate,
+            15 => MessageType::CancelNative,
+            16 => MessageType::CancelToken,
+            17 => MessageType::DirectTransfer,
+            18 => MessageType::InitializePda,
+            19 => MessageType::InitializeTokenAccount,
+            20 => MessageType::StreamCreated,
+            21 => MessageType::StreamCancelled,
+            22 => MessageType::WithdrawalCompleted,
+            23 => MessageType::ExecutionFailed,
+            24 => MessageType::TransferMemo,
+            25 => MessageType::BatchTokenStream,
+            26 => MessageType::BatchStreamsProcessed,
+            _ => return err!(MessengerError::UnknownPayloadType),
+        };
+        Ok(message_type)
+    }
+}
+
+/// A payload with a fixed message type and body length.
+pub trait XstreamPayload: DeserializePayload + SerializePayload {
+    const MESSAGE_TYPE: MessageType;
+    /// Body length, or the length of the fixed part for payloads ending with a list
+    const LENGTH: usize;
+
+    /// Wormhole chain id (big-endian uint256) the message was sent to.
+    fn to_chain_id(&self) -> &[u8; 32];
+}
+
+/// Checks the payload length, message type and version, returning a cursor positioned at the
+/// first field.
+fn read_header<T: XstreamPayload>(buf: &[u8]) -> Result<Cursor<&[u8]>> {
+    read_header_with_length::<T>(buf, T::LENGTH)
+}
+
+/// `read_header` for payloads whose body is `length` bytes long.
+fn read_header_with_length<T: XstreamPayload>(buf: &[u8], length: usize) -> Result<Cursor<&[u8]>> {
+    require!(
+        buf.len() == PAYLOAD_HEADER_LENGTH + length,
+        MessengerError::InvalidPayloadLength
+    );
+    if buf[0] != T::MESSAGE_TYPE as u8 {
+        msg!(
+            "payload type {} cannot be handled by {}",
+            buf[0],
+            T::MESSAGE_TYPE.handler()
+        );
+        return err!(MessengerError::MessageTypeMismatch);
+    }
+    require!(
+        buf[1] == PAYLOAD_VERSION,
+        MessengerError::UnsupportedPayloadVersion
+    );
+
+    let mut v = Cursor::new(buf);
+    v.set_position(PAYLOAD_HEADER_LENGTH as u64);
+    Ok(v)
+}
+
+fn write_header<T: XstreamPayload, W: Write>(writer: &mut W) -> Result<()> {
+    writer.write_u8(T::MESSAGE_TYPE as u8)?;
+    writer.write_u8(PAYLOAD_VERSION)?;
+    Ok(())
+}
+
+/// Encodes a Wormhole chain id the way `to_chain_id` is carried, as a big-endian uint256.
+pub fn encode_chain_id(chain_id: u16) -> [u8; 32] {
+    let mut data = [0; 32];
+    data[30..].copy_from_slice(&chain_id.to_be_bytes());
+    data
+}
+
+fn read_bytes32(v: &mut Cursor<&[u8]>) -> Result<[u8; 32]> {
+    let mut data = [0; 32];
+    v.read_exact(&mut data)?;
+    Ok(data)
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct InitializePdaPayload {
+    pub account: [u8; 32],
+    pub to_chain_id: [u8; 32],
+}
+
+impl XstreamPayload for InitializePdaPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::InitializePda;
+    const LENGTH: usize = 32 + 32;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for InitializePdaPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let account = read_bytes32(&mut v)?;
+        let to_chain_id = read_bytes32(&mut v)?;
+
+        Ok(InitializePdaPayload {
+            account,
+            to_chain_id,
+        })
+    }
+}
+
+impl SerializePayload for InitializePdaPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.account)?;
+        writer.write_all(&self.to_chain_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct InitializeTokenAccountPayload {
+    pub account: [u8; 32],
+    pub token_mint: [u8; 32],
+    pub to_chain_id: [u8; 32],
+}
+
+impl XstreamPayload for InitializeTokenAccountPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::InitializeTokenAccount;
+    const LENGTH: usize = 32 + 32 + 32;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for InitializeTokenAccountPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let account = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let to_chain_id = read_bytes32(&mut v)?;
+
+        Ok(InitializeTokenAccountPayload {
+            account,
+            token_mint,
+            to_chain_id,
+        })
+    }
+}
+
+impl SerializePayload for InitializeTokenAccountPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.account)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.to_chain_id)?;
+
+        Ok(())
+    }
+}
+
+/// How the `receiver` of a stream or instant transfer maps to a Solana account.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum ReceiverKind {
+    /// PDA of the receiver and the source chain, acting through VAAs of that chain
+    ChainPda = 0,
+    /// Solana wallet, which withdraws by signing zebec instructions itself
+    SolanaWallet = 1,
+}
+
+impl TryFrom<u8> for ReceiverKind {
+    type Error = Error;
+
+    fn try_from(code: u8) -> Result<Self> {
+        match code {
+            0 => Ok(ReceiverKind::ChainPda),
+            1 => Ok(ReceiverKind::SolanaWallet),
+            _ => err!(MessengerError::InvalidReceiverKind),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamStartPayload {
     pub start_time: u64,
     pub end_time: u64,
@@ -8,20 +291,134 @@ pub struct XstreamStartPayload {
     pub to_chain_id: [u8; 32],
     pub sender: [u8; 32],
     pub receiver: [u8; 32],
-    pub can_update: u64,
     pub can_cancel: u64,
+    pub can_update: u64,
     pub token_mint: [u8; 32],
+    /// Picked by the sender, seeds the stream data account
+    pub stream_id: u64,
+    /// Decimals `amount` is expressed in, scaled to the mint decimals on Solana
+    pub decimals: u8,
+    pub receiver_kind: ReceiverKind,
+}
+
+impl XstreamPayload for XstreamStartPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::TokenStream;
+    const LENGTH: usize = 8 + 8 + 8 + 32 + 32 + 32 + 8 + 8 + 32 + 8 + 1 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamStartPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let start_time = v.read_u64::<BigEndian>()?;
+        let end_time = v.read_u64::<BigEndian>()?;
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let can_cancel = v.read_u64::<BigEndian>()?;
+        let can_update = v.read_u64::<BigEndian>()?;
+        let token_mint = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+        let decimals = v.read_u8()?;
+        let receiver_kind = ReceiverKind::try_from(v.read_u8()?)?;
+
+        Ok(XstreamStartPayload {
+            start_time,
+            end_time,
+            amount,
+            to_chain_id,
+            sender,
+            receiver,
+            can_cancel,
+            can_update,
+            token_mint,
+            stream_id,
+            decimals,
+            receiver_kind,
+        })
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+impl SerializePayload for XstreamStartPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.start_time)?;
+        writer.write_u64::<BigEndian>(self.end_time)?;
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.can_cancel)?;
+        writer.write_u64::<BigEndian>(self.can_update)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+        writer.write_u8(self.decimals)?;
+        writer.write_u8(self.receiver_kind as u8)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamDepositPayload {
     pub amount: u64,
     pub to_chain_id: [u8; 32],
     pub sender: [u8; 32],
     pub token_mint: [u8; 32],
+    /// Decimals `amount` is expressed in, scaled to the mint decimals on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamDepositPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::DepositToken;
+    const LENGTH: usize = 8 + 32 + 32 + 32 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+impl DeserializePayload for XstreamDepositPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamDepositPayload {
+            amount,
+            to_chain_id,
+            sender,
+            token_mint,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamDepositPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamUpdatePayload {
     pub start_time: u64,
     pub end_time: u64,
@@ -30,59 +427,1928 @@ pub struct XstreamUpdatePayload {
     pub sender: [u8; 32],
     pub receiver: [u8; 32],
     pub token_mint: [u8; 32],
-    pub data_account: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+    /// Decimals `amount` is expressed in, scaled to the mint decimals on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamUpdatePayload {
+    const MESSAGE_TYPE: MessageType = MessageType::TokenStreamUpdate;
+    const LENGTH: usize = 8 + 8 + 8 + 32 + 32 + 32 + 32 + 8 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamUpdatePayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let start_time = v.read_u64::<BigEndian>()?;
+        let end_time = v.read_u64::<BigEndian>()?;
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamUpdatePayload {
+            start_time,
+            end_time,
+            amount,
+            to_chain_id,
+            sender,
+            receiver,
+            token_mint,
+            stream_id,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamUpdatePayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.start_time)?;
+        writer.write_u64::<BigEndian>(self.end_time)?;
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamPausePayload {
     pub to_chain_id: [u8; 32],
     pub depositor: [u8; 32],
     pub token_mint: [u8; 32],
     pub receiver: [u8; 32],
-    pub data_account: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+impl XstreamPayload for XstreamPausePayload {
+    const MESSAGE_TYPE: MessageType = MessageType::PauseToken;
+    const LENGTH: usize = 32 + 32 + 32 + 32 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamPausePayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+
+        Ok(XstreamPausePayload {
+            to_chain_id,
+            depositor,
+            token_mint,
+            receiver,
+            stream_id,
+        })
+    }
+}
+
+impl SerializePayload for XstreamPausePayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamWithdrawPayload {
     pub to_chain_id: [u8; 32],
     pub withdrawer: [u8; 32],
     pub token_mint: [u8; 32],
     pub depositor: [u8; 32],
-    pub data_account: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+}
+
+impl XstreamPayload for XstreamWithdrawPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::TokenWithdrawStream;
+    const LENGTH: usize = 32 + 32 + 32 + 32 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+impl DeserializePayload for XstreamWithdrawPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let withdrawer = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+
+        Ok(XstreamWithdrawPayload {
+            to_chain_id,
+            withdrawer,
+            token_mint,
+            depositor,
+            stream_id,
+        })
+    }
+}
+
+impl SerializePayload for XstreamWithdrawPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.withdrawer)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamCancelPayload {
     pub to_chain_id: [u8; 32],
     pub depositor: [u8; 32],
     pub token_mint: [u8; 32],
     pub receiver: [u8; 32],
-    pub data_account: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+}
+
+impl XstreamPayload for XstreamCancelPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::CancelToken;
+    const LENGTH: usize = 32 + 32 + 32 + 32 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+impl DeserializePayload for XstreamCancelPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+
+        Ok(XstreamCancelPayload {
+            to_chain_id,
+            depositor,
+            token_mint,
+            receiver,
+            stream_id,
+        })
+    }
+}
+
+impl SerializePayload for XstreamCancelPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamWithdrawDepositPayload {
     pub amount: u64,
     pub to_chain_id: [u8; 32],
     pub withdrawer: [u8; 32],
     pub token_mint: [u8; 32],
+    /// Decimals `amount` is expressed in, scaled to the mint decimals on Solana
+    pub decimals: u8,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+impl XstreamPayload for XstreamWithdrawDepositPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::WithdrawToken;
+    const LENGTH: usize = 8 + 32 + 32 + 32 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamWithdrawDepositPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let withdrawer = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamWithdrawDepositPayload {
+            amount,
+            to_chain_id,
+            withdrawer,
+            token_mint,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamWithdrawDepositPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.withdrawer)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamInstantTransferPayload {
     pub amount: u64,
     pub to_chain_id: [u8; 32],
     pub sender: [u8; 32],
     pub token_mint: [u8; 32],
     pub receiver: [u8; 32],
+    /// Decimals `amount` is expressed in, scaled to the mint decimals on Solana
+    pub decimals: u8,
+    pub receiver_kind: ReceiverKind,
+}
+
+impl XstreamPayload for XstreamInstantTransferPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::InstantToken;
+    const LENGTH: usize = 8 + 32 + 32 + 32 + 32 + 1 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamInstantTransferPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+        let receiver_kind = ReceiverKind::try_from(v.read_u8()?)?;
+
+        Ok(XstreamInstantTransferPayload {
+            amount,
+            to_chain_id,
+            sender,
+            token_mint,
+            receiver,
+            decimals,
+            receiver_kind,
+        })
+    }
+}
+
+impl SerializePayload for XstreamInstantTransferPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u8(self.decimals)?;
+        writer.write_u8(self.receiver_kind as u8)?;
+
+        Ok(())
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct XstreamDirectTransferPayload {
     pub amount: u64,
     pub to_chain_id: [u8; 32],
     pub sender: [u8; 32],
     pub token_mint: [u8; 32],
     pub receiver: [u8; 32],
+    /// Highest Token Bridge fee the relayer may take out of `amount`
+    pub max_relayer_fee: u64,
+    /// Wormhole chain the funds are bridged to
+    pub target_chain: u16,
+    /// Decimals of `amount` and `max_relayer_fee`, scaled to the mint decimals on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamDirectTransferPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::DirectTransfer;
+    const LENGTH: usize = 8 + 32 + 32 + 32 + 32 + 8 + 2 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamDirectTransferPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let max_relayer_fee = v.read_u64::<BigEndian>()?;
+        let target_chain = v.read_u16::<BigEndian>()?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamDirectTransferPayload {
+            amount,
+            to_chain_id,
+            sender,
+            token_mint,
+            receiver,
+            max_relayer_fee,
+            target_chain,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamDirectTransferPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.max_relayer_fee)?;
+        writer.write_u16::<BigEndian>(self.target_chain)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeStartPayload {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub amount: u64,
+    pub to_chain_id: [u8; 32],
+    pub sender: [u8; 32],
+    pub receiver: [u8; 32],
+    pub can_cancel: u64,
+    pub can_update: u64,
+    /// Picked by the sender, seeds the stream data account
+    pub stream_id: u64,
+    /// Decimals `amount` is expressed in, scaled to lamports on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamNativeStartPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::NativeStream;
+    const LENGTH: usize = 8 + 8 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeStartPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let start_time = v.read_u64::<BigEndian>()?;
+        let end_time = v.read_u64::<BigEndian>()?;
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let can_cancel = v.read_u64::<BigEndian>()?;
+        let can_update = v.read_u64::<BigEndian>()?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamNativeStartPayload {
+            start_time,
+            end_time,
+            amount,
+            to_chain_id,
+            sender,
+            receiver,
+            can_cancel,
+            can_update,
+            stream_id,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeStartPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.start_time)?;
+        writer.write_u64::<BigEndian>(self.end_time)?;
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.can_cancel)?;
+        writer.write_u64::<BigEndian>(self.can_update)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeUpdatePayload {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub amount: u64,
+    pub to_chain_id: [u8; 32],
+    pub sender: [u8; 32],
+    pub receiver: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+    /// Decimals `amount` is expressed in, scaled to lamports on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamNativeUpdatePayload {
+    const MESSAGE_TYPE: MessageType = MessageType::NativeStreamUpdate;
+    const LENGTH: usize = 8 + 8 + 8 + 32 + 32 + 32 + 8 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeUpdatePayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let start_time = v.read_u64::<BigEndian>()?;
+        let end_time = v.read_u64::<BigEndian>()?;
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamNativeUpdatePayload {
+            start_time,
+            end_time,
+            amount,
+            to_chain_id,
+            sender,
+            receiver,
+            stream_id,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeUpdatePayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.start_time)?;
+        writer.write_u64::<BigEndian>(self.end_time)?;
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeDepositPayload {
+    pub amount: u64,
+    pub to_chain_id: [u8; 32],
+    pub sender: [u8; 32],
+    /// Decimals `amount` is expressed in, scaled to lamports on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamNativeDepositPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::DepositSol;
+    const LENGTH: usize = 8 + 32 + 32 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeDepositPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamNativeDepositPayload {
+            amount,
+            to_chain_id,
+            sender,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeDepositPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativePausePayload {
+    pub to_chain_id: [u8; 32],
+    pub depositor: [u8; 32],
+    pub receiver: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+}
+
+impl XstreamPayload for XstreamNativePausePayload {
+    const MESSAGE_TYPE: MessageType = MessageType::PauseNative;
+    const LENGTH: usize = 32 + 32 + 32 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
 }
 
+impl DeserializePayload for XstreamNativePausePayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+
+        Ok(XstreamNativePausePayload {
+            to_chain_id,
+            depositor,
+            receiver,
+            stream_id,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativePausePayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeWithdrawPayload {
+    pub to_chain_id: [u8; 32],
+    pub withdrawer: [u8; 32],
+    pub depositor: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+}
+
+impl XstreamPayload for XstreamNativeWithdrawPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::NativeWithdrawStream;
+    const LENGTH: usize = 32 + 32 + 32 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeWithdrawPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let withdrawer = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+
+        Ok(XstreamNativeWithdrawPayload {
+            to_chain_id,
+            withdrawer,
+            depositor,
+            stream_id,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeWithdrawPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.withdrawer)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeCancelPayload {
+    pub to_chain_id: [u8; 32],
+    pub depositor: [u8; 32],
+    pub receiver: [u8; 32],
+    /// Id the stream was started with
+    pub stream_id: u64,
+}
+
+impl XstreamPayload for XstreamNativeCancelPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::CancelNative;
+    const LENGTH: usize = 32 + 32 + 32 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeCancelPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let stream_id = v.read_u64::<BigEndian>()?;
+
+        Ok(XstreamNativeCancelPayload {
+            to_chain_id,
+            depositor,
+            receiver,
+            stream_id,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeCancelPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.stream_id)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeWithdrawDepositPayload {
+    pub amount: u64,
+    pub to_chain_id: [u8; 32],
+    pub withdrawer: [u8; 32],
+    /// Decimals `amount` is expressed in, scaled to lamports on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamNativeWithdrawDepositPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::WithdrawSol;
+    const LENGTH: usize = 8 + 32 + 32 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeWithdrawDepositPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let withdrawer = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamNativeWithdrawDepositPayload {
+            amount,
+            to_chain_id,
+            withdrawer,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeWithdrawDepositPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.withdrawer)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamNativeInstantTransferPayload {
+    pub amount: u64,
+    pub to_chain_id: [u8; 32],
+    pub sender: [u8; 32],
+    pub receiver: [u8; 32],
+    /// Decimals `amount` is expressed in, scaled to lamports on Solana
+    pub decimals: u8,
+}
+
+impl XstreamPayload for XstreamNativeInstantTransferPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::InstantNative;
+    const LENGTH: usize = 8 + 32 + 32 + 32 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamNativeInstantTransferPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let amount = v.read_u64::<BigEndian>()?;
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+
+        Ok(XstreamNativeInstantTransferPayload {
+            amount,
+            to_chain_id,
+            sender,
+            receiver,
+            decimals,
+        })
+    }
+}
+
+impl SerializePayload for XstreamNativeInstantTransferPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u8(self.decimals)?;
+
+        Ok(())
+    }
+}
+
+/// Posted when a stream requested by `sequence` has been created on Solana.
+#[derive(PartialEq, Debug, Clone)]
+pub struct StreamCreatedPayload {
+    pub to_chain_id: [u8; 32],
+    /// Sequence of the VAA that requested the stream
+    pub sequence: u64,
+    pub sender: [u8; 32],
+    pub receiver: [u8; 32],
+    pub token_mint: [u8; 32],
+    pub data_account: [u8; 32],
+    pub amount: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+impl XstreamPayload for StreamCreatedPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::StreamCreated;
+    const LENGTH: usize = 32 + 8 + 32 + 32 + 32 + 32 + 8 + 8 + 8;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for StreamCreatedPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sequence = v.read_u64::<BigEndian>()?;
+        let sender = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let data_account = read_bytes32(&mut v)?;
+        let amount = v.read_u64::<BigEndian>()?;
+        let start_time = v.read_u64::<BigEndian>()?;
+        let end_time = v.read_u64::<BigEndian>()?;
+
+        Ok(StreamCreatedPayload {
+            to_chain_id,
+            sequence,
+            sender,
+            receiver,
+            token_mint,
+            data_account,
+            amount,
+            start_time,
+            end_time,
+        })
+    }
+}
+
+impl SerializePayload for StreamCreatedPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_u64::<BigEndian>(self.sequence)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.data_account)?;
+        writer.write_u64::<BigEndian>(self.amount)?;
+        writer.write_u64::<BigEndian>(self.start_time)?;
+        writer.write_u64::<BigEndian>(self.end_time)?;
+
+        Ok(())
+    }
+}
+
+/// Posted when a stream cancel requested by `sequence` has been executed on Solana.
+#[derive(PartialEq, Debug, Clone)]
+pub struct StreamCancelledPayload {
+    pub to_chain_id: [u8; 32],
+    /// Sequence of the VAA that requested the cancel
+    pub sequence: u64,
+    pub depositor: [u8; 32],
+    pub receiver: [u8; 32],
+    pub token_mint: [u8; 32],
+    pub data_account: [u8; 32],
+}
+
+impl XstreamPayload for StreamCancelledPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::StreamCancelled;
+    const LENGTH: usize = 32 + 8 + 32 + 32 + 32 + 32;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for StreamCancelledPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sequence = v.read_u64::<BigEndian>()?;
+        let depositor = read_bytes32(&mut v)?;
+        let receiver = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let data_account = read_bytes32(&mut v)?;
+
+        Ok(StreamCancelledPayload {
+            to_chain_id,
+            sequence,
+            depositor,
+            receiver,
+            token_mint,
+            data_account,
+        })
+    }
+}
+
+impl SerializePayload for StreamCancelledPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_u64::<BigEndian>(self.sequence)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.data_account)?;
+
+        Ok(())
+    }
+}
+
+/// Posted when a stream withdrawal requested by `sequence` has been executed on Solana.
+#[derive(PartialEq, Debug, Clone)]
+pub struct WithdrawalCompletedPayload {
+    pub to_chain_id: [u8; 32],
+    /// Sequence of the VAA that requested the withdrawal
+    pub sequence: u64,
+    pub withdrawer: [u8; 32],
+    pub depositor: [u8; 32],
+    pub token_mint: [u8; 32],
+    pub data_account: [u8; 32],
+}
+
+impl XstreamPayload for WithdrawalCompletedPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::WithdrawalCompleted;
+    const LENGTH: usize = 32 + 8 + 32 + 32 + 32 + 32;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for WithdrawalCompletedPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sequence = v.read_u64::<BigEndian>()?;
+        let withdrawer = read_bytes32(&mut v)?;
+        let depositor = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let data_account = read_bytes32(&mut v)?;
+
+        Ok(WithdrawalCompletedPayload {
+            to_chain_id,
+            sequence,
+            withdrawer,
+            depositor,
+            token_mint,
+            data_account,
+        })
+    }
+}
+
+impl SerializePayload for WithdrawalCompletedPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_u64::<BigEndian>(self.sequence)?;
+        writer.write_all(&self.withdrawer)?;
+        writer.write_all(&self.depositor)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_all(&self.data_account)?;
+
+        Ok(())
+    }
+}
+
+/// Posted by a relayer that gave up on the VAA with `sequence`, the VAA is marked as processed.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ExecutionFailedPayload {
+    pub to_chain_id: [u8; 32],
+    /// Sequence of the VAA that could not be executed
+    pub sequence: u64,
+    /// Code of the message carried by that VAA
+    pub message_type: u8,
+    /// Program error the execution failed with
+    pub error_code: u32,
+}
+
+impl XstreamPayload for ExecutionFailedPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::ExecutionFailed;
+    const LENGTH: usize = 32 + 8 + 1 + 4;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for ExecutionFailedPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sequence = v.read_u64::<BigEndian>()?;
+        let message_type = v.read_u8()?;
+        let error_code = v.read_u32::<BigEndian>()?;
+
+        Ok(ExecutionFailedPayload {
+            to_chain_id,
+            sequence,
+            message_type,
+            error_code,
+        })
+    }
+}
+
+impl SerializePayload for ExecutionFailedPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_u64::<BigEndian>(self.sequence)?;
+        writer.write_u8(self.message_type)?;
+        writer.write_u32::<BigEndian>(self.error_code)?;
+
+        Ok(())
+    }
+}
+
+/// One stream of a `XstreamBatchStartPayload`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BatchStreamItem {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub amount: u64,
+    pub receiver: [u8; 32],
+    pub can_cancel: u64,
+    pub can_update: u64,
+    pub stream_id: u64,
+    pub receiver_kind: ReceiverKind,
+}
+
+impl BatchStreamItem {
+    pub const LENGTH: usize = 8 + 8 + 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+/// Token streams started by one sender in a single VAA, such as a payroll.
+#[derive(PartialEq, Debug, Clone)]
+pub struct XstreamBatchStartPayload {
+    pub to_chain_id: [u8; 32],
+    pub sender: [u8; 32],
+    pub token_mint: [u8; 32],
+    /// Decimals the item amounts are expressed in, scaled to the mint decimals on Solana
+    pub decimals: u8,
+    /// Between 1 and `MAX_BATCH_SIZE` items, prefixed by their count on the wire
+    pub items: Vec<BatchStreamItem>,
+}
+
+impl XstreamPayload for XstreamBatchStartPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::BatchTokenStream;
+    const LENGTH: usize = 32 + 32 + 32 + 1 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for XstreamBatchStartPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let count = match buf.get(PAYLOAD_HEADER_LENGTH + Self::LENGTH - 1) {
+            Some(count) => *count as usize,
+            None => return err!(MessengerError::InvalidPayloadLength),
+        };
+        require!(
+            count > 0 && count <= MAX_BATCH_SIZE,
+            MessengerError::InvalidCount
+        );
+        let mut v =
+            read_header_with_length::<Self>(buf, Self::LENGTH + count * BatchStreamItem::LENGTH)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let token_mint = read_bytes32(&mut v)?;
+        let decimals = v.read_u8()?;
+        v.read_u8()?;
+
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            let start_time = v.read_u64::<BigEndian>()?;
+            let end_time = v.read_u64::<BigEndian>()?;
+            let amount = v.read_u64::<BigEndian>()?;
+            let receiver = read_bytes32(&mut v)?;
+            let can_cancel = v.read_u64::<BigEndian>()?;
+            let can_update = v.read_u64::<BigEndian>()?;
+            let stream_id = v.read_u64::<BigEndian>()?;
+            let receiver_kind = ReceiverKind::try_from(v.read_u8()?)?;
+            items.push(BatchStreamItem {
+                start_time,
+                end_time,
+                amount,
+                receiver,
+                can_cancel,
+                can_update,
+                stream_id,
+                receiver_kind,
+            });
+        }
+
+        Ok(XstreamBatchStartPayload {
+            to_chain_id,
+            sender,
+            token_mint,
+            decimals,
+            items,
+        })
+    }
+}
+
+impl SerializePayload for XstreamBatchStartPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.token_mint)?;
+        writer.write_u8(self.decimals)?;
+        writer.write_u8(self.items.len() as u8)?;
+        for item in &self.items {
+            writer.write_u64::<BigEndian>(item.start_time)?;
+            writer.write_u64::<BigEndian>(item.end_time)?;
+            writer.write_u64::<BigEndian>(item.amount)?;
+            writer.write_all(&item.receiver)?;
+            writer.write_u64::<BigEndian>(item.can_cancel)?;
+            writer.write_u64::<BigEndian>(item.can_update)?;
+            writer.write_u64::<BigEndian>(item.stream_id)?;
+            writer.write_u8(item.receiver_kind as u8)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of one stream of a batch, `error_code` is 0 when the stream was started.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BatchStreamResult {
+    pub stream_id: u64,
+    pub data_account: [u8; 32],
+    pub amount: u64,
+    pub error_code: u32,
+}
+
+impl BatchStreamResult {
+    pub const LENGTH: usize = 8 + 32 + 8 + 4;
+}
+
+/// Posted once a batch requested by `sequence` has been processed on Solana, with the outcome
+/// of every stream in the order of the batch.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BatchStreamsProcessedPayload {
+    pub to_chain_id: [u8; 32],
+    /// Sequence of the VAA that requested the batch
+    pub sequence: u64,
+    /// Between 1 and `MAX_BATCH_SIZE` results, prefixed by their count on the wire
+    pub results: Vec<BatchStreamResult>,
+}
+
+impl XstreamPayload for BatchStreamsProcessedPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::BatchStreamsProcessed;
+    const LENGTH: usize = 32 + 8 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for BatchStreamsProcessedPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let count = match buf.get(PAYLOAD_HEADER_LENGTH + Self::LENGTH - 1) {
+            Some(count) => *count as usize,
+            None => return err!(MessengerError::InvalidPayloadLength),
+        };
+        require!(
+            count > 0 && count <= MAX_BATCH_SIZE,
+            MessengerError::InvalidCount
+        );
+        let mut v =
+            read_header_with_length::<Self>(buf, Self::LENGTH + count * BatchStreamResult::LENGTH)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sequence = v.read_u64::<BigEndian>()?;
+        v.read_u8()?;
+
+        let mut results = Vec::with_capacity(count);
+        for _ in 0..count {
+            let stream_id = v.read_u64::<BigEndian>()?;
+            let data_account = read_bytes32(&mut v)?;
+            let amount = v.read_u64::<BigEndian>()?;
+            let error_code = v.read_u32::<BigEndian>()?;
+            results.push(BatchStreamResult {
+                stream_id,
+                data_account,
+                amount,
+                error_code,
+            });
+        }
+
+        Ok(BatchStreamsProcessedPayload {
+            to_chain_id,
+            sequence,
+            results,
+        })
+    }
+}
+
+impl SerializePayload for BatchStreamsProcessedPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_u64::<BigEndian>(self.sequence)?;
+        writer.write_u8(self.results.len() as u8)?;
+        for result in &self.results {
+            writer.write_u64::<BigEndian>(result.stream_id)?;
+            writer.write_all(&result.data_account)?;
+            writer.write_u64::<BigEndian>(result.amount)?;
+            writer.write_u32::<BigEndian>(result.error_code)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Why funds were sent back to the source chain.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum TransferPurpose {
+    DirectTransfer = 1,
+}
+
+/// Zebec memo carried by Token Bridge transfers with payload sent to the Messenger, which
+/// forwards the funds to `receiver`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TransferMemoPayload {
+    pub to_chain_id: [u8; 32],
+    /// Account on the source chain that requested the transfer
+    pub sender: [u8; 32],
+    pub sender_chain: u16,
+    pub receiver: [u8; 32],
+    /// Sequence of the VAA that requested the transfer
+    pub request_sequence: u64,
+    /// A `TransferPurpose`
+    pub purpose: u8,
+}
+
+impl XstreamPayload for TransferMemoPayload {
+    const MESSAGE_TYPE: MessageType = MessageType::TransferMemo;
+    const LENGTH: usize = 32 + 32 + 2 + 32 + 8 + 1;
+
+    fn to_chain_id(&self) -> &[u8; 32] {
+        &self.to_chain_id
+    }
+}
+
+impl DeserializePayload for TransferMemoPayload {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let mut v = read_header::<Self>(buf)?;
+
+        let to_chain_id = read_bytes32(&mut v)?;
+        let sender = read_bytes32(&mut v)?;
+        let sender_chain = v.read_u16::<BigEndian>()?;
+        let receiver = read_bytes32(&mut v)?;
+        let request_sequence = v.read_u64::<BigEndian>()?;
+        let purpose = v.read_u8()?;
+
+        Ok(TransferMemoPayload {
+            to_chain_id,
+            sender,
+            sender_chain,
+            receiver,
+            request_sequence,
+            purpose,
+        })
+    }
+}
+
+impl SerializePayload for TransferMemoPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_header::<Self, W>(writer)?;
+
+        writer.write_all(&self.to_chain_id)?;
+        writer.write_all(&self.sender)?;
+        writer.write_u16::<BigEndian>(self.sender_chain)?;
+        writer.write_all(&self.receiver)?;
+        writer.write_u64::<BigEndian>(self.request_sequence)?;
+        writer.write_u8(self.purpose)?;
+
+        Ok(())
+    }
+}
+
+/// Every xstream message, keyed by its message code.
+#[derive(PartialEq, Debug, Clone)]
+pub enum XstreamMessage {
+    InitializePda(InitializePdaPayload),
+    InitializeTokenAccount(InitializeTokenAccountPayload),
+    Start(XstreamStartPayload),
+    Withdraw(XstreamWithdrawPayload),
+    Deposit(XstreamDepositPayload),
+    Pause(XstreamPausePayload),
+    SenderWithdraw(XstreamWithdrawDepositPayload),
+    InstantTransfer(XstreamInstantTransferPayload),
+    Update(XstreamUpdatePayload),
+    Cancel(XstreamCancelPayload),
+    DirectTransfer(XstreamDirectTransferPayload),
+    NativeStart(XstreamNativeStartPayload),
+    NativeWithdraw(XstreamNativeWithdrawPayload),
+    NativeDeposit(XstreamNativeDepositPayload),
+    NativePause(XstreamNativePausePayload),
+    NativeSenderWithdraw(XstreamNativeWithdrawDepositPayload),
+    NativeInstantTransfer(XstreamNativeInstantTransferPayload),
+    NativeUpdate(XstreamNativeUpdatePayload),
+    NativeCancel(XstreamNativeCancelPayload),
+    StreamCreated(StreamCreatedPayload),
+    StreamCancelled(StreamCancelledPayload),
+    WithdrawalCompleted(WithdrawalCompletedPayload),
+    ExecutionFailed(ExecutionFailedPayload),
+    TransferMemo(TransferMemoPayload),
+    BatchStart(XstreamBatchStartPayload),
+    BatchStreamsProcessed(BatchStreamsProcessedPayload),
+}
+
+impl DeserializePayload for XstreamMessage {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        require!(
+            buf.len() >= PAYLOAD_HEADER_LENGTH,
+            MessengerError::InvalidPayloadLength
+        );
+
+        let message = match MessageType::try_from(buf[0])? {
+            MessageType::TokenStream => {
+                XstreamMessage::Start(XstreamStartPayload::deserialize(buf)?)
+            }
+            MessageType::TokenWithdrawStream => {
+                XstreamMessage::Withdraw(XstreamWithdrawPayload::deserialize(buf)?)
+            }
+            MessageType::DepositToken => {
+                XstreamMessage::Deposit(XstreamDepositPayload::deserialize(buf)?)
+            }
+            MessageType::PauseToken => {
+                XstreamMessage::Pause(XstreamPausePayload::deserialize(buf)?)
+            }
+            MessageType::WithdrawToken => {
+                XstreamMessage::SenderWithdraw(XstreamWithdrawDepositPayload::deserialize(buf)?)
+            }
+            MessageType::InstantToken => {
+                XstreamMessage::InstantTransfer(XstreamInstantTransferPayload::deserialize(buf)?)
+            }
+            MessageType::TokenStreamUpdate => {
+                XstreamMessage::Update(XstreamUpdatePayload::deserialize(buf)?)
+            }
+            MessageType::CancelToken => {
+                XstreamMessage::Cancel(XstreamCancelPayload::deserialize(buf)?)
+            }
+            MessageType::DirectTransfer => {
+                XstreamMessage::DirectTransfer(XstreamDirectTransferPayload::deserialize(buf)?)
+            }
+            MessageType::InitializePda => {
+                XstreamMessage::InitializePda(InitializePdaPayload::deserialize(buf)?)
+            }
+            MessageType::InitializeTokenAccount => XstreamMessage::InitializeTokenAccount(
+                InitializeTokenAccountPayload::deserialize(buf)?,
+            ),
+            MessageType::NativeStream => {
+                XstreamMessage::NativeStart(XstreamNativeStartPayload::deserialize(buf)?)
+            }
+            MessageType::NativeWithdrawStream => {
+                XstreamMessage::NativeWithdraw(XstreamNativeWithdrawPayload::deserialize(buf)?)
+            }
+            MessageType::DepositSol => {
+                XstreamMessage::NativeDeposit(XstreamNativeDepositPayload::deserialize(buf)?)
+            }
+            MessageType::PauseNative => {
+                XstreamMessage::NativePause(XstreamNativePausePayload::deserialize(buf)?)
+            }
+            MessageType::WithdrawSol => XstreamMessage::NativeSenderWithdraw(
+                XstreamNativeWithdrawDepositPayload::deserialize(buf)?,
+            ),
+            MessageType::InstantNative => XstreamMessage::NativeInstantTransfer(
+                XstreamNativeInstantTransferPayload::deserialize(buf)?,
+            ),
+            MessageType::NativeStreamUpdate => {
+                XstreamMessage::NativeUpdate(XstreamNativeUpdatePayload::deserialize(buf)?)
+            }
+            MessageType::CancelNative => {
+                XstreamMessage::NativeCancel(XstreamNativeCancelPayload::deserialize(buf)?)
+            }
+            MessageType::StreamCreated => {
+                XstreamMessage::StreamCreated(StreamCreatedPayload::deserialize(buf)?)
+            }
+            MessageType::StreamCancelled => {
+                XstreamMessage::StreamCancelled(StreamCancelledPayload::deserialize(buf)?)
+            }
+            MessageType::WithdrawalCompleted => {
+                XstreamMessage::WithdrawalCompleted(WithdrawalCompletedPayload::deserialize(buf)?)
+            }
+            MessageType::ExecutionFailed => {
+                XstreamMessage::ExecutionFailed(ExecutionFailedPayload::deserialize(buf)?)
+            }
+            MessageType::TransferMemo => {
+                XstreamMessage::TransferMemo(TransferMemoPayload::deserialize(buf)?)
+            }
+            MessageType::BatchTokenStream => {
+                XstreamMessage::BatchStart(XstreamBatchStartPayload::deserialize(buf)?)
+            }
+            MessageType::BatchStreamsProcessed => XstreamMessage::BatchStreamsProcessed(
+                BatchStreamsProcessedPayload::deserialize(buf)?,
+            ),
+        };
+        Ok(message)
+    }
+}
+
+impl XstreamMessage {
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            XstreamMessage::InitializePda(_) => MessageType::InitializePda,
+            XstreamMessage::InitializeTokenAccount(_) => MessageType::InitializeTokenAccount,
+            XstreamMessage::Start(_) => MessageType::TokenStream,
+            XstreamMessage::Withdraw(_) => MessageType::TokenWithdrawStream,
+            XstreamMessage::Deposit(_) => MessageType::DepositToken,
+            XstreamMessage::Pause(_) => MessageType::PauseToken,
+            XstreamMessage::SenderWithdraw(_) => MessageType::WithdrawToken,
+            XstreamMessage::InstantTransfer(_) => MessageType::InstantToken,
+            XstreamMessage::Update(_) => MessageType::TokenStreamUpdate,
+            XstreamMessage::Cancel(_) => MessageType::CancelToken,
+            XstreamMessage::DirectTransfer(_) => MessageType::DirectTransfer,
+            XstreamMessage::NativeStart(_) => MessageType::NativeStream,
+            XstreamMessage::NativeWithdraw(_) => MessageType::NativeWithdrawStream,
+            XstreamMessage::NativeDeposit(_) => MessageType::DepositSol,
+            XstreamMessage::NativePause(_) => MessageType::PauseNative,
+            XstreamMessage::NativeSenderWithdraw(_) => MessageType::WithdrawSol,
+            XstreamMessage::NativeInstantTransfer(_) => MessageType::InstantNative,
+            XstreamMessage::NativeUpdate(_) => MessageType::NativeStreamUpdate,
+            XstreamMessage::NativeCancel(_) => MessageType::CancelNative,
+            XstreamMessage::StreamCreated(_) => MessageType::StreamCreated,
+            XstreamMessage::StreamCancelled(_) => MessageType::StreamCancelled,
+            XstreamMessage::WithdrawalCompleted(_) => MessageType::WithdrawalCompleted,
+            XstreamMessage::ExecutionFailed(_) => MessageType::ExecutionFailed,
+            XstreamMessage::TransferMemo(_) => MessageType::TransferMemo,
+            XstreamMessage::BatchStart(_) => MessageType::BatchTokenStream,
+            XstreamMessage::BatchStreamsProcessed(_) => MessageType::BatchStreamsProcessed,
+        }
+    }
+}
+
+impl SerializePayload for XstreamMessage {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            XstreamMessage::InitializePda(payload) => payload.serialize(writer),
+            XstreamMessage::InitializeTokenAccount(payload) => payload.serialize(writer),
+            XstreamMessage::Start(payload) => payload.serialize(writer),
+            XstreamMessage::Withdraw(payload) => payload.serialize(writer),
+            XstreamMessage::Deposit(payload) => payload.serialize(writer),
+            XstreamMessage::Pause(payload) => payload.serialize(writer),
+            XstreamMessage::SenderWithdraw(payload) => payload.serialize(writer),
+            XstreamMessage::InstantTransfer(payload) => payload.serialize(writer),
+            XstreamMessage::Update(payload) => payload.serialize(writer),
+            XstreamMessage::Cancel(payload) => payload.serialize(writer),
+            XstreamMessage::DirectTransfer(payload) => payload.serialize(writer),
+            XstreamMessage::NativeStart(payload) => payload.serialize(writer),
+            XstreamMessage::NativeWithdraw(payload) => payload.serialize(writer),
+            XstreamMessage::NativeDeposit(payload) => payload.serialize(writer),
+            XstreamMessage::NativePause(payload) => payload.serialize(writer),
+            XstreamMessage::NativeSenderWithdraw(payload) => payload.serialize(writer),
+            XstreamMessage::NativeInstantTransfer(payload) => payload.serialize(writer),
+            XstreamMessage::NativeUpdate(payload) => payload.serialize(writer),
+            XstreamMessage::NativeCancel(payload) => payload.serialize(writer),
+            XstreamMessage::StreamCreated(payload) => payload.serialize(writer),
+            XstreamMessage::StreamCancelled(payload) => payload.serialize(writer),
+            XstreamMessage::WithdrawalCompleted(payload) => payload.serialize(writer),
+            XstreamMessage::ExecutionFailed(payload) => payload.serialize(writer),
+            XstreamMessage::TransferMemo(payload) => payload.serialize(writer),
+            XstreamMessage::BatchStart(payload) => payload.serialize(writer),
+            XstreamMessage::BatchStreamsProcessed(payload) => payload.serialize(writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes32(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    fn to_chain_id() -> [u8; 32] {
+        encode_chain_id(1)
+    }
+
+    fn token_start() -> XstreamStartPayload {
+        XstreamStartPayload {
+            start_time: 1_700_000_000,
+            end_time: 1_700_086_400,
+            amount: 1_000_000,
+            to_chain_id: to_chain_id(),
+            sender: bytes32(0x11),
+            receiver: bytes32(0x22),
+            can_cancel: 1,
+            can_update: 0,
+            token_mint: bytes32(0x33),
+            stream_id: 7,
+            decimals: 6,
+            receiver_kind: ReceiverKind::SolanaWallet,
+        }
+    }
+
+    fn batch_item(stream_id: u64) -> BatchStreamItem {
+        BatchStreamItem {
+            start_time: 1_700_000_000,
+            end_time: 1_700_086_400,
+            amount: 500,
+            receiver: bytes32(0x44),
+            can_cancel: 0,
+            can_update: 1,
+            stream_id,
+            receiver_kind: ReceiverKind::ChainPda,
+        }
+    }
+
+    /// One message of every type.
+    fn messages() -> Vec<XstreamMessage> {
+        vec![
+            XstreamMessage::InitializePda(InitializePdaPayload {
+                account: bytes32(0x11),
+                to_chain_id: to_chain_id(),
+            }),
+            XstreamMessage::InitializeTokenAccount(InitializeTokenAccountPayload {
+                account: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                to_chain_id: to_chain_id(),
+            }),
+            XstreamMessage::Start(token_start()),
+            XstreamMessage::Withdraw(XstreamWithdrawPayload {
+                to_chain_id: to_chain_id(),
+                withdrawer: bytes32(0x22),
+                token_mint: bytes32(0x33),
+                depositor: bytes32(0x11),
+                stream_id: 7,
+            }),
+            XstreamMessage::Deposit(XstreamDepositPayload {
+                amount: 1_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                decimals: 6,
+            }),
+            XstreamMessage::Pause(XstreamPausePayload {
+                to_chain_id: to_chain_id(),
+                depositor: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                receiver: bytes32(0x22),
+                stream_id: 7,
+            }),
+            XstreamMessage::SenderWithdraw(XstreamWithdrawDepositPayload {
+                amount: 1_000_000,
+                to_chain_id: to_chain_id(),
+                withdrawer: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                decimals: 6,
+            }),
+            XstreamMessage::InstantTransfer(XstreamInstantTransferPayload {
+                amount: 1_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                receiver: bytes32(0x22),
+                decimals: 6,
+                receiver_kind: ReceiverKind::ChainPda,
+            }),
+            XstreamMessage::Update(XstreamUpdatePayload {
+                start_time: 1_700_000_000,
+                end_time: 1_700_172_800,
+                amount: 2_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                receiver: bytes32(0x22),
+                token_mint: bytes32(0x33),
+                stream_id: 7,
+                decimals: 6,
+            }),
+            XstreamMessage::Cancel(XstreamCancelPayload {
+                to_chain_id: to_chain_id(),
+                depositor: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                receiver: bytes32(0x22),
+                stream_id: 7,
+            }),
+            XstreamMessage::DirectTransfer(XstreamDirectTransferPayload {
+                amount: 1_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                receiver: bytes32(0x22),
+                max_relayer_fee: 1_000,
+                target_chain: 2,
+                decimals: 6,
+            }),
+            XstreamMessage::NativeStart(XstreamNativeStartPayload {
+                start_time: 1_700_000_000,
+                end_time: 1_700_086_400,
+                amount: 1_000_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                receiver: bytes32(0x22),
+                can_cancel: 1,
+                can_update: 0,
+                stream_id: 8,
+                decimals: 9,
+            }),
+            XstreamMessage::NativeWithdraw(XstreamNativeWithdrawPayload {
+                to_chain_id: to_chain_id(),
+                withdrawer: bytes32(0x22),
+                depositor: bytes32(0x11),
+                stream_id: 8,
+            }),
+            XstreamMessage::NativeDeposit(XstreamNativeDepositPayload {
+                amount: 1_000_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                decimals: 9,
+            }),
+            XstreamMessage::NativePause(XstreamNativePausePayload {
+                to_chain_id: to_chain_id(),
+                depositor: bytes32(0x11),
+                receiver: bytes32(0x22),
+                stream_id: 8,
+            }),
+            XstreamMessage::NativeSenderWithdraw(XstreamNativeWithdrawDepositPayload {
+                amount: 1_000_000_000,
+                to_chain_id: to_chain_id(),
+                withdrawer: bytes32(0x11),
+                decimals: 9,
+            }),
+            XstreamMessage::NativeInstantTransfer(XstreamNativeInstantTransferPayload {
+                amount: 1_000_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                receiver: bytes32(0x22),
+                decimals: 9,
+            }),
+            XstreamMessage::NativeUpdate(XstreamNativeUpdatePayload {
+                start_time: 1_700_000_000,
+                end_time: 1_700_172_800,
+                amount: 2_000_000_000,
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                receiver: bytes32(0x22),
+                stream_id: 8,
+                decimals: 9,
+            }),
+            XstreamMessage::NativeCancel(XstreamNativeCancelPayload {
+                to_chain_id: to_chain_id(),
+                depositor: bytes32(0x11),
+                receiver: bytes32(0x22),
+                stream_id: 8,
+            }),
+            XstreamMessage::StreamCreated(StreamCreatedPayload {
+                to_chain_id: encode_chain_id(2),
+                sequence: 42,
+                sender: bytes32(0x11),
+                receiver: bytes32(0x22),
+                token_mint: bytes32(0x33),
+                data_account: bytes32(0x55),
+                amount: 1_000_000,
+                start_time: 1_700_000_000,
+                end_time: 1_700_086_400,
+            }),
+            XstreamMessage::StreamCancelled(StreamCancelledPayload {
+                to_chain_id: encode_chain_id(2),
+                sequence: 43,
+                depositor: bytes32(0x11),
+                receiver: bytes32(0x22),
+                token_mint: bytes32(0x33),
+                data_account: bytes32(0x55),
+            }),
+            XstreamMessage::WithdrawalCompleted(WithdrawalCompletedPayload {
+                to_chain_id: encode_chain_id(2),
+                sequence: 44,
+                withdrawer: bytes32(0x22),
+                depositor: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                data_account: bytes32(0x55),
+            }),
+            XstreamMessage::ExecutionFailed(ExecutionFailedPayload {
+                to_chain_id: encode_chain_id(2),
+                sequence: 45,
+                message_type: MessageType::TokenStream as u8,
+                error_code: 6000,
+            }),
+            XstreamMessage::TransferMemo(TransferMemoPayload {
+                to_chain_id: encode_chain_id(2),
+                sender: bytes32(0x11),
+                sender_chain: 2,
+                receiver: bytes32(0x22),
+                request_sequence: 46,
+                purpose: TransferPurpose::DirectTransfer as u8,
+            }),
+            XstreamMessage::BatchStart(XstreamBatchStartPayload {
+                to_chain_id: to_chain_id(),
+                sender: bytes32(0x11),
+                token_mint: bytes32(0x33),
+                decimals: 6,
+                items: vec![batch_item(9), batch_item(10)],
+            }),
+            XstreamMessage::BatchStreamsProcessed(BatchStreamsProcessedPayload {
+                to_chain_id: encode_chain_id(2),
+                sequence: 47,
+                results: vec![
+                    BatchStreamResult {
+                        stream_id: 9,
+                        data_account: bytes32(0x55),
+                        amount: 500,
+                        error_code: 0,
+                    },
+                    BatchStreamResult {
+                        stream_id: 10,
+                        data_account: bytes32(0x66),
+                        amount: 0,
+                        error_code: 6001,
+                    },
+                ],
+            }),
+        ]
+    }
+
+    fn assert_error<T: std::fmt::Debug>(result: Result<T>, error: MessengerError) {
+        assert_eq!(result.unwrap_err(), Error::from(error));
+    }
+
+    #[test]
+    fn roundtrip_every_message_type() {
+        let messages = messages();
+        let mut codes: Vec<u8> = messages.iter().map(|m| m.message_type() as u8).collect();
+        codes.sort_unstable();
+        assert_eq!(codes, (1..=26).collect::<Vec<u8>>());
+
+        for message in messages {
+            let encoded = message.try_to_vec().unwrap();
+            assert_eq!(encoded[0], message.message_type() as u8);
+            assert_eq!(encoded[1], PAYLOAD_VERSION);
+            assert_eq!(
+                XstreamMessage::deserialize(&mut encoded.as_slice()).unwrap(),
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn reject_truncated_payloads() {
+        assert_error(
+            XstreamMessage::deserialize(&mut [].as_slice()),
+            MessengerError::InvalidPayloadLength,
+        );
+        for message in messages() {
+            let encoded = message.try_to_vec().unwrap();
+            for length in 1..encoded.len() {
+                assert!(
+                    XstreamMessage::deserialize(&mut &encoded[..length]).is_err(),
+                    "{:?} truncated to {} bytes",
+                    message.message_type(),
+                    length
+                );
+            }
+            assert_error(
+                XstreamMessage::deserialize(&mut &encoded[..encoded.len() - 1]),
+                MessengerError::InvalidPayloadLength,
+            );
+        }
+    }
+
+    #[test]
+    fn reject_trailing_bytes() {
+        for message in messages() {
+            let mut encoded = message.try_to_vec().unwrap();
+            encoded.push(0);
+            assert_error(
+                XstreamMessage::deserialize(&mut encoded.as_slice()),
+                MessengerError::InvalidPayloadLength,
+            );
+        }
+    }
+
+    #[test]
+    fn reject_wrong_code() {
+        let mut encoded = token_start().try_to_vec().unwrap();
+        encoded[0] = MessageType::NativeStream as u8;
+        assert_error(
+            XstreamStartPayload::deserialize(&mut encoded.as_slice()),
+            MessengerError::MessageTypeMismatch,
+        );
+
+        for code in [0, 27, u8::MAX] {
+            encoded[0] = code;
+            assert_error(
+                XstreamMessage::deserialize(&mut encoded.as_slice()),
+                MessengerError::UnknownPayloadType,
+            );
+        }
+    }
+
+    #[test]
+    fn reject_wrong_version() {
+        for message in messages() {
+            let mut encoded = message.try_to_vec().unwrap();
+            encoded[1] = PAYLOAD_VERSION + 1;
+            assert_error(
+                XstreamMessage::deserialize(&mut encoded.as_slice()),
+                MessengerError::UnsupportedPayloadVersion,
+            );
+        }
+    }
+
+    #[test]
+    fn can_cancel_precedes_can_update() {
+        // header, start_time, end_time, amount, to_chain_id, sender, receiver
+        let offset = PAYLOAD_HEADER_LENGTH + 8 + 8 + 8 + 32 + 32 + 32;
+
+        let encoded = token_start().try_to_vec().unwrap();
+        assert_eq!(encoded[offset..offset + 8], 1u64.to_be_bytes());
+        assert_eq!(encoded[offset + 8..offset + 16], 0u64.to_be_bytes());
+
+        let native = XstreamNativeStartPayload {
+            start_time: 1_700_000_000,
+            end_time: 1_700_086_400,
+            amount: 1_000_000_000,
+            to_chain_id: to_chain_id(),
+            sender: bytes32(0x11),
+            receiver: bytes32(0x22),
+            can_cancel: 0,
+            can_update: 1,
+            stream_id: 8,
+            decimals: 9,
+        };
+        let encoded = native.try_to_vec().unwrap();
+        assert_eq!(encoded[offset..offset + 8], 0u64.to_be_bytes());
+        assert_eq!(encoded[offset + 8..offset + 16], 1u64.to_be_bytes());
+    }
+
+    #[test]
+    fn decode_evm_token_stream() {
+        // Encoder.encode_token_stream of token_start(), as produced by abi.encodePacked
+        let encoded = hex::decode(concat!(
+            "02",                                                               // TOKEN_STREAM
+            "01",                                                               // PAYLOAD_VERSION
+            "000000006553f100",                                                 // start_time
+            "0000000065554280",                                                 // end_time
+            "00000000000f4240",                                                 // amount
+            "0000000000000000000000000000000000000000000000000000000000000001", // toChain
+            "1111111111111111111111111111111111111111111111111111111111111111", // sender
+            "2222222222222222222222222222222222222222222222222222222222222222", // receiver
+            "0000000000000001",                                                 // can_cancel
+            "0000000000000000",                                                 // can_update
+            "3333333333333333333333333333333333333333333333333333333333333333", // token_mint
+            "0000000000000007",                                                 // stream_id
+            "06",                                                               // decimals
+            "01",                                                               // receiver_kind
+        ))
+        .unwrap();
+
+        assert_eq!(
+            XstreamStartPayload::deserialize(&mut encoded.as_slice()).unwrap(),
+            token_start()
+        );
+        assert_eq!(token_start().try_to_vec().unwrap(), encoded);
+    }
+}
@@ -309,6 +309,26 @@ pub struct TransferNativeData {
     pub target_chain: u16,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Default)]
+pub struct TransferWrappedWithPayloadData {
+    pub nonce: u32,
+    pub amount: u64,
+    pub target_address: [u8; 32],
+    pub target_chain: u16,
+    pub payload: Vec<u8>,
+    pub cpi_program_id: Option<Pubkey>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Default)]
+pub struct TransferNativeWithPayloadData {
+    pub nonce: u32,
+    pub amount: u64,
+    pub target_address: [u8; 32],
+    pub target_chain: u16,
+    pub payload: Vec<u8>,
+    pub cpi_program_id: Option<Pubkey>,
+}
+
 #[derive(Clone)]
 pub struct TokenPortalBridge;
 
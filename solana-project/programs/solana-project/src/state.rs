@@ -2,26 +2,218 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use borsh::{BorshDeserialize, BorshSerialize};
 
+use crate::constants::{EVM_ADDRESS_LENGTH, MAX_RELAYERS, SEQUENCES_PER_BITMAP};
+
 #[account]
 #[derive(Default)]
 pub struct Config {
     pub owner: Pubkey,
     pub nonce: u32,
+    // Wormhole chain id of this deployment, every inbound payload must be addressed to it
+    pub local_chain_id: u16,
+    // Proposed by the owner, takes over once it calls accept_owner
+    pub pending_owner: Pubkey,
+    // Registers and manages emitters
+    pub admin: Pubkey,
+    // Can halt VAA processing
+    pub pauser: Pubkey,
+    // Accounts allowed to submit VAAs
+    pub relayers: Vec<Pubkey>,
+    // Emergency switch, no VAA is processed while set
+    pub paused: bool,
+    // Lamports above rent exemption a sender PDA is funded with
+    pub pda_funding: u64,
+    // Sender PDAs holding less than this above rent exemption can be topped up
+    pub pda_top_up_threshold: u64,
+    // Seconds before a ProcessedVAA can be closed
+    pub vaa_retention: i64,
+    // CONFIG_VERSION the account was written with
+    pub version: u8,
+}
+
+impl Config {
+    pub const LEN: usize =
+        8 + 32 + 4 + 2 + 32 + 32 + 32 + 4 + 32 * MAX_RELAYERS + 1 + 8 + 8 + 8 + 1;
+
+    pub fn is_relayer(&self, key: &Pubkey) -> bool {
+        self.relayers.contains(key)
+    }
+}
+
+// Config as first deployed, before the chain id, roles and policies were added. Only read by
+// migrate_config.
+#[derive(AnchorDeserialize)]
+pub struct LegacyConfig {
+    pub owner: Pubkey,
+    pub nonce: u32,
+}
+
+impl LegacyConfig {
+    pub const LEN: usize = 8 + 32 + 4;
 }
 
 #[account]
 #[derive(Default)]
 pub struct EmitterAddrAccount {
+    pub chain_id: u16,
+    // Emitter address as it appears in the VAA
+    pub emitter_addr: [u8; 32],
+    pub address_format: AddressFormat,
+    pub status: EmitterStatus,
+    // Emergency switch for VAAs of this emitter only
+    pub paused: bool,
+    // Weakest finality accepted from this emitter
+    pub min_consistency_level: u8,
+    // Max seconds between publication and execution, 0 disables the check
+    pub max_vaa_age: u32,
+}
+
+// EmitterAddrAccount as first deployed, seeded by the chain id alone and holding the hex encoded
+// address. Only read by migrate_emitter.
+#[derive(AnchorDeserialize)]
+pub struct LegacyEmitterAddrAccount {
     pub chain_id: u16,
     pub emitter_addr: String,
 }
 
-//Empty account, we just need to check that it *exists*
+// Native address format of the emitter's chain, all of them are carried as 32 bytes by Wormhole
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    Evm,
+    Solana,
+    Aptos,
+    Sui,
+    CosmosBech32,
+}
+
+impl Default for AddressFormat {
+    fn default() -> Self {
+        AddressFormat::Evm
+    }
+}
+
+impl AddressFormat {
+    pub fn is_valid(&self, address: &[u8; 32]) -> bool {
+        if address.iter().all(|byte| *byte == 0) {
+            return false;
+        }
+        match self {
+            AddressFormat::Evm => address[..32 - EVM_ADDRESS_LENGTH]
+                .iter()
+                .all(|byte| *byte == 0),
+            AddressFormat::Solana
+            | AddressFormat::Aptos
+            | AddressFormat::Sui
+            | AddressFormat::CosmosBech32 => true,
+        }
+    }
+}
+
+// Only Active emitters can have their VAAs executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EmitterStatus {
+    Active,
+    Disabled,
+}
+
+impl Default for EmitterStatus {
+    fn default() -> Self {
+        EmitterStatus::Active
+    }
+}
+
+// Lamports the operator has sent to a sender PDA, kept to recover costs
+#[account]
+#[derive(Default)]
+pub struct PdaSponsorship {
+    pub pda: Pubkey,
+    pub sponsored: u64,
+}
+
+impl PdaSponsorship {
+    pub const LEN: usize = 8 + 32 + 8;
+}
+
+// Relayer fee policy for direct transfers to `chain_id`
+#[account]
+#[derive(Default)]
+pub struct FeeSchedule {
+    pub chain_id: u16,
+    // Highest relayer fee taken on a transfer to this chain, whatever the signed cap. Kept in
+    // Token Bridge units of TOKEN_BRIDGE_MAX_DECIMALS so one schedule fits every mint.
+    pub max_fee: u64,
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 8 + 2 + 8;
+}
+
+// Receipt of a consumed VAA, closable once the retention period is over
 #[account]
 pub struct ProcessedVAA {
+    // Message code of the payload
+    pub message_type: u8,
+    // Slot the VAA was consumed in
+    pub slot: u64,
+    // Paid for the account and gets its rent back on close
+    pub relayer: Pubkey,
+    pub processed_at: i64,
+    pub status: VaaStatus,
+    // Why the VAA was rejected, 0 when executed
+    pub error_code: u32,
+}
+
+impl ProcessedVAA {
+    pub const LEN: usize = 8 + 1 + 8 + 32 + 8 + 1 + 4;
+}
+
+// Rejected VAAs are permanently invalid, they are consumed without being executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VaaStatus {
+    Executed,
+    Rejected,
+}
+
+// ProcessedVAA as first deployed, before execution records and the sequence bitmaps. Only read by
+// close_legacy_processed_vaa.
+#[derive(AnchorDeserialize)]
+pub struct LegacyProcessedVAA {
     pub transaction_count: u64,
 }
 
+impl LegacyProcessedVAA {
+    pub const LEN: usize = 8 + 8;
+}
+
+// Executed sequences of an emitter, from `bucket * SEQUENCES_PER_BITMAP` on. This is the replay
+// protection, so ProcessedVAA accounts can be closed.
+#[account]
+pub struct SequenceBitmap {
+    pub chain_id: u16,
+    pub emitter_addr: [u8; 32],
+    pub bucket: u64,
+    pub bits: [u8; (SEQUENCES_PER_BITMAP / 8) as usize],
+}
+
+impl SequenceBitmap {
+    pub const LEN: usize = 8 + 2 + 32 + 8 + (SEQUENCES_PER_BITMAP / 8) as usize;
+
+    fn position(sequence: u64) -> (usize, u8) {
+        let offset = sequence % SEQUENCES_PER_BITMAP;
+        ((offset / 8) as usize, 1 << (offset % 8))
+    }
+
+    pub fn is_set(&self, sequence: u64) -> bool {
+        let (index, mask) = Self::position(sequence);
+        self.bits[index] & mask != 0
+    }
+
+    pub fn set(&mut self, sequence: u64) {
+        let (index, mask) = Self::position(sequence);
+        self.bits[index] |= mask;
+    }
+}
+
 #[account]
 pub struct Transaction {
     //450
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use byteorder::{BigEndian, WriteBytesExt};
+use sha3::Digest;
+use std::{
+    io::{Cursor, Write},
+    str::FromStr,
+};
+
+use crate::constants::{CORE_BRIDGE_ADDRESS, SEQUENCES_PER_BITMAP};
+use crate::errors::MessengerError;
+use crate::state::{
+    Config, EmitterAddrAccount, EmitterStatus, ProcessedVAA, SequenceBitmap, VaaStatus,
+};
+use crate::wormhole::{MessageData, PostedMessageData};
+
+/// A posted VAA that has passed every check in `verify_vaa`.
+#[derive(Debug, Clone)]
+pub struct ParsedVaa {
+    /// Keccak256 of the signed body, seeds the Core Bridge PostedVAA account
+    pub hash: [u8; 32],
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub nonce: u32,
+    pub consistency_level: u8,
+    /// Time the message was published on the emitter chain
+    pub timestamp: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Checks that `core_bridge_vaa` is the Core Bridge PostedVAA account of a message sent by the
+/// registered emitter on `from_chain_id`, that neither the program nor the emitter is paused, and
+/// that the message meets the emitter's minimum consistency level and maximum age.
+///
+/// `from_chain_id` is the chain the instruction derives the sender PDA with, so a VAA can only act
+/// on PDAs of its own chain. The owner of `core_bridge_vaa` is checked by the account constraints
+/// of every instruction.
+pub fn verify_vaa(
+    core_bridge_vaa: &AccountInfo,
+    emitter_acc: &EmitterAddrAccount,
+    config: &Config,
+    from_chain_id: u16,
+) -> Result<ParsedVaa> {
+    let vaa = parse_posted_vaa(core_bridge_vaa)?;
+    check_emitter(&vaa, &vaa.emitter_address, emitter_acc, config)?;
+    require!(
+        vaa.emitter_chain == from_chain_id,
+        MessengerError::VAAEmitterMismatch
+    );
+    Ok(vaa)
+}
+
+/// Reads `core_bridge_vaa` and checks that it is the Core Bridge PostedVAA account of its body.
+pub fn parse_posted_vaa(core_bridge_vaa: &AccountInfo) -> Result<ParsedVaa> {
+    let vaa = PostedMessageData::try_from_slice(&core_bridge_vaa.data.borrow())?.0;
+    let serialized_vaa = serialize_vaa(&vaa);
+
+    let mut h = sha3::Keccak256::default();
+    h.write_all(serialized_vaa.as_slice()).unwrap();
+    let vaa_hash: [u8; 32] = h.finalize().into();
+
+    let (vaa_key, _) = Pubkey::find_program_address(
+        &[b"PostedVAA", &vaa_hash],
+        &Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap(),
+    );
+    require!(
+        core_bridge_vaa.key() == vaa_key,
+        MessengerError::VAAKeyMismatch
+    );
+
+    Ok(ParsedVaa {
+        hash: vaa_hash,
+        sequence: vaa.sequence,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        nonce: vaa.nonce,
+        consistency_level: vaa.consistency_level,
+        timestamp: vaa.vaa_time,
+        payload: vaa.payload,
+    })
+}
+
+/// Checks that `sender` on the VAA's emitter chain is the registered emitter and that the VAA
+/// meets its settings. `sender` is the VAA emitter for Messenger messages, and the `from_address`
+/// of Token Bridge transfers with payload.
+pub fn check_emitter(
+    vaa: &ParsedVaa,
+    sender: &[u8; 32],
+    emitter_acc: &EmitterAddrAccount,
+    config: &Config,
+) -> Result<()> {
+    require!(!config.paused, MessengerError::ProgramPaused);
+
+    // Check that the emitter chain and address match up with the vaa
+    require!(
+        vaa.emitter_chain == emitter_acc.chain_id && *sender == emitter_acc.emitter_addr,
+        MessengerError::VAAEmitterMismatch
+    );
+    require!(
+        emitter_acc.status == EmitterStatus::Active,
+        MessengerError::EmitterDisabled
+    );
+    require!(!emitter_acc.paused, MessengerError::EmitterPaused);
+
+    require!(
+        vaa.consistency_level >= emitter_acc.min_consistency_level,
+        MessengerError::ConsistencyLevelTooLow
+    );
+    if emitter_acc.max_vaa_age > 0 {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= vaa.timestamp as i64 + emitter_acc.max_vaa_age as i64,
+            MessengerError::VAAExpired
+        );
+    }
+
+    Ok(())
+}
+
+/// Marks the VAA sequence as executed in the emitter's bitmap, failing if it already was, and
+/// fills in the ProcessedVAA receipt.
+pub fn record_vaa(
+    vaa: &ParsedVaa,
+    sequence_bitmap: &mut SequenceBitmap,
+    processed_vaa: &mut ProcessedVAA,
+    relayer: Pubkey,
+) -> Result<()> {
+    // Freshly created bitmap
+    if sequence_bitmap.emitter_addr == [0; 32] {
+        sequence_bitmap.chain_id = vaa.emitter_chain;
+        sequence_bitmap.emitter_addr = vaa.emitter_address;
+        sequence_bitmap.bucket = vaa.sequence / SEQUENCES_PER_BITMAP;
+    }
+    require!(
+        !sequence_bitmap.is_set(vaa.sequence),
+        MessengerError::AlreadyExecuted
+    );
+    sequence_bitmap.set(vaa.sequence);
+
+    let clock = Clock::get()?;
+    processed_vaa.message_type = vaa.payload.first().copied().unwrap_or_default();
+    processed_vaa.slot = clock.slot;
+    processed_vaa.relayer = relayer;
+    processed_vaa.processed_at = clock.unix_timestamp;
+    processed_vaa.status = VaaStatus::Executed;
+    processed_vaa.error_code = 0;
+    Ok(())
+}
+
+// Convert a full VAA structure into the serialization of its unique components, this structure is
+// what is hashed and verified by Guardians.
+pub fn serialize_vaa(vaa: &MessageData) -> Vec<u8> {
+    let mut v = Cursor::new(Vec::new());
+    v.write_u32::<BigEndian>(vaa.vaa_time).unwrap();
+    v.write_u32::<BigEndian>(vaa.nonce).unwrap();
+    v.write_u16::<BigEndian>(vaa.emitter_chain as u16).unwrap();
+    v.write_all(&vaa.emitter_address).unwrap();
+    v.write_u64::<BigEndian>(vaa.sequence).unwrap();
+    v.write_u8(vaa.consistency_level).unwrap();
+    v.write_all(&vaa.payload).unwrap();
+    v.into_inner()
+}
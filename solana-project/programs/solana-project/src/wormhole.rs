@@ -1,8 +1,16 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction as SolanaInstruction,
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use std::{io::Write, str::FromStr};
 
 use crate::constants::CORE_BRIDGE_ADDRESS;
+use crate::context::WormholeOutbound;
+use crate::errors::MessengerError;
+use crate::state::Config;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct PostMessageData {
@@ -118,3 +126,80 @@ impl anchor_lang::Id for WormholeCoreBridge {
         Pubkey::from_str(CORE_BRIDGE_ADDRESS).unwrap()
     }
 }
+
+/// Posts `payload` through the Core Bridge from the program emitter. The message fee is paid by
+/// `payer` and `Config.nonce` is used as the message nonce.
+pub fn post_message<'info>(
+    outbound: &WormholeOutbound<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    config: &mut Config,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let bridge = BridgeData::try_from_slice(&outbound.bridge_config.data.borrow())?;
+    if bridge.config.fee > 0 {
+        invoke(
+            &system_instruction::transfer(
+                payer.key,
+                outbound.bridge_fee_collector.key,
+                bridge.config.fee,
+            ),
+            &[
+                payer.clone(),
+                outbound.bridge_fee_collector.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    let post_message_ix = SolanaInstruction {
+        program_id: outbound.core_bridge_program.key(),
+        accounts: vec![
+            AccountMeta::new(outbound.bridge_config.key(), false),
+            AccountMeta::new(outbound.wormhole_message.key(), true),
+            AccountMeta::new_readonly(outbound.wormhole_emitter.key(), true),
+            AccountMeta::new(outbound.wormhole_sequence.key(), false),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new(outbound.bridge_fee_collector.key(), false),
+            AccountMeta::new_readonly(outbound.clock.key(), false),
+            AccountMeta::new_readonly(outbound.rent.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ],
+        data: (
+            Instruction::PostMessage,
+            PostMessageData {
+                nonce: config.nonce,
+                payload,
+                consistency_level: ConsistencyLevel::Finalized,
+            },
+        )
+            .try_to_vec()?,
+    };
+
+    let post_message_accs = vec![
+        outbound.bridge_config.to_account_info(),
+        outbound.wormhole_message.to_account_info(),
+        outbound.wormhole_emitter.to_account_info(),
+        outbound.wormhole_sequence.to_account_info(),
+        payer.clone(),
+        outbound.bridge_fee_collector.to_account_info(),
+        outbound.clock.to_account_info(),
+        outbound.rent.to_account_info(),
+        system_program.clone(),
+        outbound.core_bridge_program.to_account_info(),
+    ];
+
+    let (_, bump) = Pubkey::find_program_address(&[b"emitter"], &crate::ID);
+    invoke_signed(
+        &post_message_ix,
+        &post_message_accs,
+        &[&[b"emitter", &[bump]]],
+    )?;
+
+    config.nonce = config
+        .nonce
+        .checked_add(1)
+        .ok_or(MessengerError::Overflow)?;
+
+    Ok(())
+}
@@ -171,6 +171,7 @@ const store_msg_direct_transfer = async () => {
     [
       Buffer.from("EmitterAddress"),
       b.serializeUint16(parsed_vaa.emitter_chain),
+      Buffer.from(parsed_vaa.emitter_address),
     ],
     program.programId
   )[0];
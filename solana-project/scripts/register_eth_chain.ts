@@ -36,16 +36,27 @@ async function register_eth_address() {
   );
 
   await program.methods
-    .registerChain(CHAIN_ID_BSC, ethAddress)
+    .registerChain(
+      CHAIN_ID_BSC,
+      [...Buffer.from(ethAddress, 'hex')],
+      { evm: {} },
+      // Finalized source blocks only, executed within a day of publication
+      1,
+      24 * 60 * 60
+    )
     .accounts({
-      owner: KEYPAIR.publicKey,
+      admin: KEYPAIR.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
       config: findProgramAddressSync(
         [Buffer.from('config')],
         program.programId
       )[0],
       emitterAcc: findProgramAddressSync(
-        [Buffer.from('EmitterAddress'), b.serializeUint16(CHAIN_ID_BSC)],
+        [
+          Buffer.from('EmitterAddress'),
+          b.serializeUint16(CHAIN_ID_BSC),
+          Buffer.from(ethAddress, 'hex'),
+        ],
         program.programId
       )[0],
     })
@@ -226,6 +226,7 @@ const store_msg_deposit = async () => {
     [
       Buffer.from('EmitterAddress'),
       b.serializeUint16(parsed_vaa.emitter_chain),
+      Buffer.from(parsed_vaa.emitter_address),
     ],
     program.programId
   )[0];